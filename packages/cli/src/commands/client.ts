@@ -10,21 +10,29 @@ import chalk from 'chalk';
 import ora from 'ora';
 import { join } from 'path';
 import { access } from 'fs/promises';
+import { exitWithError, printResult, type OutputOptions } from '../lib/output.js';
 
-interface ClientOptions {
+export interface ClientOptions extends OutputOptions {
   config: string;
   role?: string;
 }
 
+/**
+ * Subset of the MCP client used by the REPL (injectable for tests)
+ */
+export type ToolClient = Pick<Client, 'callTool' | 'listTools' | 'close'>;
+
 /**
  * MCP Client - connects to a Mycelium MCP server
  */
-class MyceliumClient {
-  private client: Client | null = null;
+export class MyceliumClient {
+  private client: ToolClient | null = null;
   private rl: readline.Interface | null = null;
   private isRunning = false;
 
-  constructor(private options: ClientOptions) {}
+  constructor(private options: ClientOptions, client?: ToolClient) {
+    this.client = client ?? null;
+  }
 
   async connect(): Promise<void> {
     const projectRoot = process.cwd();
@@ -63,12 +71,13 @@ class MyceliumClient {
       });
 
       // Create MCP client
-      this.client = new Client(
+      const client = new Client(
         { name: 'mycelium-client', version: '1.0.0' },
         { capabilities: {} }
       );
 
-      await this.client.connect(transport);
+      await client.connect(transport);
+      this.client = client;
       spinner.succeed('Connected to Mycelium MCP server');
 
       // Show initial status
@@ -93,12 +102,14 @@ class MyceliumClient {
       const text = content.find(c => c.type === 'text')?.text || '{}';
       const context = JSON.parse(text);
 
-      console.log(chalk.cyan('\n📊 Status:'));
-      console.log(`  Role: ${chalk.green(context.currentRole || 'unknown')}`);
-      console.log(`  Skills: ${chalk.yellow(context.activeSkills?.join(', ') || 'none')}`);
-      console.log(`  Tools: ${chalk.blue(context.visibleToolCount || 0)} available\n`);
+      printResult(this.options, context, () => [
+        chalk.cyan('\n📊 Status:'),
+        `  Role: ${chalk.green(context.currentRole || 'unknown')}`,
+        `  Skills: ${chalk.yellow(context.activeSkills?.join(', ') || 'none')}`,
+        `  Tools: ${chalk.blue(context.visibleToolCount || 0)} available\n`,
+      ].join('\n'));
     } catch (error) {
-      console.log(chalk.yellow('Could not get status'));
+      this.printFailure('Could not get status', chalk.yellow('Could not get status'));
     }
   }
 
@@ -107,16 +118,21 @@ class MyceliumClient {
 
     try {
       const result = await this.client.listTools();
-      console.log(chalk.cyan('\n🔧 Available Tools:'));
-      for (const tool of result.tools) {
-        console.log(`  ${chalk.green(tool.name)}`);
-        if (tool.description) {
-          console.log(`    ${chalk.gray(tool.description.slice(0, 60))}...`);
+      const tools = result.tools.map(({ name, description }) => ({ name, description }));
+
+      printResult(this.options, tools, () => {
+        const lines = [chalk.cyan('\n🔧 Available Tools:')];
+        for (const tool of tools) {
+          lines.push(`  ${chalk.green(tool.name)}`);
+          if (tool.description) {
+            lines.push(`    ${chalk.gray(tool.description.slice(0, 60))}...`);
+          }
         }
-      }
-      console.log(`\n  Total: ${result.tools.length} tools\n`);
+        lines.push(`\n  Total: ${tools.length} tools\n`);
+        return lines.join('\n');
+      });
     } catch (error) {
-      console.log(chalk.red('Failed to list tools'));
+      this.printFailure('Failed to list tools');
     }
   }
 
@@ -133,14 +149,16 @@ class MyceliumClient {
       const text = content.find(c => c.type === 'text')?.text || '[]';
       const skills = JSON.parse(text);
 
-      console.log(chalk.cyan('\n📚 Available Skills:'));
-      for (const skill of skills) {
-        const status = skill.isActive ? chalk.green('●') : chalk.gray('○');
-        console.log(`  ${status} ${skill.id} - ${skill.description || ''}`);
-      }
-      console.log();
+      printResult(this.options, skills, () => {
+        const lines = [chalk.cyan('\n📚 Available Skills:')];
+        for (const skill of skills) {
+          const status = skill.isActive ? chalk.green('●') : chalk.gray('○');
+          lines.push(`  ${status} ${skill.id} - ${skill.description || ''}`);
+        }
+        return `${lines.join('\n')}\n`;
+      });
     } catch (error) {
-      console.log(chalk.red('Failed to list skills'));
+      this.printFailure('Failed to list skills');
     }
   }
 
@@ -157,16 +175,120 @@ class MyceliumClient {
       const text = content.find(c => c.type === 'text')?.text || '{}';
       const response = JSON.parse(text);
 
-      if (response.success) {
-        console.log(chalk.green(`✓ Active skills: ${response.activeSkills?.join(', ')}`));
-      } else {
-        console.log(chalk.red(`✗ Failed: ${response.error}`));
+      printResult(this.options, response, () => response.success
+        ? chalk.green(`✓ Active skills: ${response.activeSkills?.join(', ')}`)
+        : chalk.red(`✗ Failed: ${response.error}`));
+    } catch (error) {
+      this.printFailure('Failed to set skills');
+    }
+  }
+
+  async listRoles(): Promise<void> {
+    if (!this.client) return;
+
+    try {
+      const result = await this.client.callTool({
+        name: 'mycelium-router__list_roles',
+        arguments: {},
+      });
+
+      const content = result.content as Array<{ type: string; text?: string }>;
+      const text = content.find(c => c.type === 'text')?.text || '{}';
+      const { roles = [] } = JSON.parse(text) as {
+        roles?: Array<{ id: string; description?: string; toolCount?: number; isCurrent?: boolean }>;
+      };
+
+      printResult(this.options, roles, () => {
+        const lines = [chalk.cyan('\n🎭 Available Roles:')];
+        for (const role of roles) {
+          const status = role.isCurrent ? chalk.green('●') : chalk.gray('○');
+          lines.push(`  ${status} ${role.id} (${role.toolCount ?? 0} tools) - ${role.description || ''}`);
+        }
+        return `${lines.join('\n')}\n`;
+      });
+    } catch (error) {
+      this.printFailure('Failed to list roles');
+    }
+  }
+
+  async setRole(roleId: string): Promise<void> {
+    if (!this.client) return;
+
+    try {
+      const result = await this.client.callTool({
+        name: 'mycelium-router__set_role',
+        arguments: { role: roleId },
+      });
+
+      const content = result.content as Array<{ type: string; text?: string }>;
+      const text = content.find(c => c.type === 'text')?.text || '{}';
+
+      if (result.isError) {
+        const message = `Failed to switch to role '${roleId}': ${text.replace(/^Error:\s*/, '')}`;
+        this.printFailure(message, chalk.red(`✗ ${message}`));
+        return;
       }
+
+      const response = JSON.parse(text);
+      printResult(this.options, response, () => chalk.green(`✓ Role: ${roleId} (${response.toolCount ?? 0} tools)`));
     } catch (error) {
-      console.log(chalk.red('Failed to set skills'));
+      this.printFailure(`Failed to set role '${roleId}'`);
     }
   }
 
+  /**
+   * Report a failed REPL command; with --json it is printed as {"error": message}
+   */
+  private printFailure(message: string, text: string = chalk.red(message)): void {
+    printResult(this.options, { error: message }, () => text);
+  }
+
+  /**
+   * Handle a single REPL input line
+   * Returns false when the REPL should exit
+   */
+  async handleCommand(input: string): Promise<boolean> {
+    const trimmed = input.trim();
+
+    if (trimmed === '/exit' || trimmed === '/quit') {
+      return false;
+    }
+
+    if (trimmed === '/tools') {
+      await this.listTools();
+    } else if (trimmed === '/skills') {
+      await this.listSkills();
+    } else if (trimmed === '/roles') {
+      await this.listRoles();
+    } else if (trimmed === '/role' || trimmed.startsWith('/role ')) {
+      const roleId = trimmed.slice(5).trim();
+      if (roleId) {
+        await this.setRole(roleId);
+      } else {
+        this.printFailure('Usage: /role <role-id>', chalk.yellow('Usage: /role <role-id>'));
+      }
+    } else if (trimmed === '/status') {
+      await this.showStatus();
+    } else if (trimmed.startsWith('/set ')) {
+      const skills = trimmed.slice(5).split(',').map(s => s.trim());
+      await this.setSkills(skills);
+    } else if (trimmed === '/help') {
+      console.log(chalk.cyan('\nCommands:'));
+      console.log('  /tools   - List available tools');
+      console.log('  /skills  - List available skills');
+      console.log('  /roles   - List available roles');
+      console.log('  /role <id> - Switch to a role');
+      console.log('  /status  - Show current status');
+      console.log('  /set <skills> - Set active skills (comma-separated)');
+      console.log('  /exit    - Exit client\n');
+    } else if (trimmed) {
+      const message = 'Unknown command. Type /help for available commands.';
+      this.printFailure(message, chalk.yellow(message));
+    }
+
+    return true;
+  }
+
   async startRepl(): Promise<void> {
     this.isRunning = true;
 
@@ -176,41 +298,19 @@ class MyceliumClient {
     });
 
     console.log(chalk.cyan('Mycelium Client REPL'));
-    console.log(chalk.gray('Commands: /tools, /skills, /status, /set <skill1,skill2>, /exit\n'));
+    console.log(chalk.gray('Commands: /tools, /skills, /roles, /role <id>, /status, /set <skill1,skill2>, /exit\n'));
 
     const prompt = () => {
       this.rl?.question(chalk.blue('myc> '), async (input) => {
         if (!this.isRunning) return;
 
-        const trimmed = input.trim();
-
-        if (trimmed === '/exit' || trimmed === '/quit') {
+        if (!(await this.handleCommand(input))) {
           this.isRunning = false;
           this.rl?.close();
           await this.disconnect();
           process.exit(0);
         }
 
-        if (trimmed === '/tools') {
-          await this.listTools();
-        } else if (trimmed === '/skills') {
-          await this.listSkills();
-        } else if (trimmed === '/status') {
-          await this.showStatus();
-        } else if (trimmed.startsWith('/set ')) {
-          const skills = trimmed.slice(5).split(',').map(s => s.trim());
-          await this.setSkills(skills);
-        } else if (trimmed === '/help') {
-          console.log(chalk.cyan('\nCommands:'));
-          console.log('  /tools   - List available tools');
-          console.log('  /skills  - List available skills');
-          console.log('  /status  - Show current status');
-          console.log('  /set <skills> - Set active skills (comma-separated)');
-          console.log('  /exit    - Exit client\n');
-        } else if (trimmed) {
-          console.log(chalk.yellow('Unknown command. Type /help for available commands.'));
-        }
-
         prompt();
       });
     };
@@ -236,8 +336,7 @@ async function runClient(options: ClientOptions): Promise<void> {
     await client.connect();
     await client.startRepl();
   } catch (error) {
-    console.error(chalk.red('Client error:'), error);
-    process.exit(1);
+    exitWithError(options, error, 'Client error:');
   }
 }
 
@@ -246,6 +345,7 @@ export const clientCommand = new Command('client')
   .description('Connect to a Mycelium MCP server (thin client mode)')
   .option('-c, --config <path>', 'Config file path', 'config.json')
   .option('-r, --role <role>', 'Role for the session', 'developer')
+  .option('--json', 'Print each command result as JSON')
   .action(async (options: ClientOptions) => {
     await runClient(options);
   });
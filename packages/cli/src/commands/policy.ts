@@ -0,0 +1,139 @@
+// ============================================================================
+// mycelium policy - Inspect skill-based policies
+// ============================================================================
+
+import { Command } from 'commander';
+import { resolve } from 'path';
+import chalk from 'chalk';
+import { loadSkillManifest } from '../lib/manifest.js';
+import { diffSkillManifests, type PolicyDiff } from '../lib/policy-diff.js';
+import { checkToolAcrossRoles, loadRoleManager, traceToolForRole, type RoleToolAccess } from '../lib/policy-check.js';
+import type { ToolAccessTrace } from '@mycelium/core';
+import { describeVisibilityReason } from '@mycelium/shared';
+import { exitWithError, printResult, type OutputOptions } from '../lib/output.js';
+
+interface CheckOptions extends OutputOptions {
+  tool: string;
+  role?: string;
+}
+
+/**
+ * Render a policy diff for terminal output
+ */
+export function formatPolicyDiff(diff: PolicyDiff): string {
+  if (diff.roles.length === 0) {
+    return chalk.gray('No role capability changes.');
+  }
+
+  const lines: string[] = [];
+
+  for (const role of diff.roles) {
+    const label = role.status === 'added'
+      ? chalk.green(`+ ${role.roleId} (new role)`)
+      : role.status === 'removed'
+        ? chalk.red(`- ${role.roleId} (removed role)`)
+        : chalk.yellow(`~ ${role.roleId}`);
+    lines.push(label);
+
+    for (const skill of role.addedSkills) lines.push(chalk.green(`    + skill ${skill}`));
+    for (const skill of role.removedSkills) lines.push(chalk.red(`    - skill ${skill}`));
+    for (const tool of role.addedTools) lines.push(chalk.green(`    + tool  ${tool}`));
+    for (const tool of role.removedTools) lines.push(chalk.red(`    - tool  ${tool}`));
+    for (const moved of role.movedTools) {
+      lines.push(chalk.cyan(
+        `    ~ tool  ${moved.tool}: [${moved.fromSkills.join(', ')}] → [${moved.toSkills.join(', ')}]`
+      ));
+    }
+  }
+
+  if (diff.unchangedRoles.length > 0) {
+    lines.push(chalk.gray(`Unchanged: ${diff.unchangedRoles.join(', ')}`));
+  }
+
+  return lines.join('\n');
+}
+
+/**
+ * Render a tool access matrix for terminal output
+ */
+export function formatToolAccess(toolName: string, results: RoleToolAccess[]): string {
+  const lines = [chalk.cyan(`Access to ${toolName}:`)];
+
+  for (const result of results) {
+    const verdict = result.allowed ? chalk.green('allow') : chalk.red('deny ');
+    lines.push(`  ${verdict}  ${result.roleId}  ${chalk.gray(describeVisibilityReason(result.reason))}`);
+  }
+
+  if (results.length === 0) {
+    lines.push(chalk.gray('  No roles defined'));
+  }
+
+  return lines.join('\n');
+}
+
+/**
+ * Render a single role's access trace for terminal output
+ */
+export function formatAccessTrace(toolName: string, roleId: string, trace: ToolAccessTrace): string {
+  const verdict = trace.allowed ? chalk.green('allow') : chalk.red('deny');
+  const lines = [
+    chalk.cyan(`Access to ${toolName} for ${roleId}:`),
+    `  ${verdict}  ${chalk.gray(describeVisibilityReason(trace.reason))}`,
+    `  chain: ${trace.chain.join(' → ') || chalk.gray('(circular)')}`
+  ];
+
+  if (trace.sourceRoleId) {
+    const label = trace.allowed ? 'granted by' : 'denied by';
+    lines.push(`  ${label}: ${trace.sourceRoleId} (${trace.rule})`);
+  }
+
+  return lines.join('\n');
+}
+
+const checkCommand = new Command('check')
+  .description('Show which roles can access a tool')
+  .argument('<manifest>', 'Skill manifest (JSON or YAML)')
+  .requiredOption('-t, --tool <name>', 'Tool name (server__tool)')
+  .option('-r, --role <id>', 'Explain the decision for one role and the rule that decided it')
+  .option('--json', 'Output the results as JSON')
+  .action(async (manifestPath: string, options: CheckOptions) => {
+    try {
+      const manifest = await loadSkillManifest(resolve(manifestPath));
+
+      const { tool, role: roleId } = options;
+
+      if (roleId) {
+        const roleManager = await loadRoleManager(manifest);
+        const trace = traceToolForRole(roleManager, roleId, tool);
+        printResult(options, { tool, roleId, ...trace }, () => formatAccessTrace(tool, roleId, trace));
+        return;
+      }
+
+      const results = await checkToolAcrossRoles(manifest, tool);
+      printResult(options, { tool, roles: results }, () => formatToolAccess(tool, results));
+    } catch (error) {
+      exitWithError(options, error);
+    }
+  });
+
+const diffCommand = new Command('diff')
+  .description('Compare role capabilities generated from two skill manifests')
+  .argument('<before>', 'Skill manifest before the change (JSON or YAML)')
+  .argument('<after>', 'Skill manifest after the change (JSON or YAML)')
+  .option('--json', 'Output the diff as JSON')
+  .action(async (before: string, after: string, options: OutputOptions) => {
+    try {
+      const [beforeManifest, afterManifest] = await Promise.all([
+        loadSkillManifest(resolve(before)),
+        loadSkillManifest(resolve(after))
+      ]);
+      printResult(options, diffSkillManifests(beforeManifest, afterManifest), formatPolicyDiff);
+    } catch (error) {
+      exitWithError(options, error);
+    }
+  });
+
+export const policyCommand = new Command('policy')
+  .description('Inspect skill-based role policies')
+  .addCommand(checkCommand)
+  .addCommand(diffCommand);
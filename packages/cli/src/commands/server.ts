@@ -11,14 +11,23 @@ import {
   ListPromptsRequestSchema,
   GetPromptRequestSchema,
 } from '@modelcontextprotocol/sdk/types.js';
-import { access, readFile } from 'fs/promises';
 import { join } from 'path';
 import chalk from 'chalk';
+import type { Logger } from '@mycelium/shared';
+import { exitWithError, printResult, type OutputOptions } from '../lib/output.js';
 
 // Import from @mycelium/core
-import { createMyceliumCore, ROUTER_TOOLS, type MyceliumCore } from '@mycelium/core';
-
-interface ServerOptions {
+import {
+  createMyceliumCore,
+  loadDesktopConfigFromFile,
+  StdioRouter,
+  ROUTER_TOOLS,
+  type DesktopConfig,
+  type MyceliumCore,
+  type ServerState
+} from '@mycelium/core';
+
+interface ServerOptions extends OutputOptions {
   config: string;
   role?: string;
   verbose?: boolean;
@@ -26,29 +35,42 @@ interface ServerOptions {
 
 /**
  * Simple logger for server mode
+ * Logs go to stderr, since stdout carries the MCP protocol; with json set each line is a JSON object
  */
-class ServerLogger {
-  constructor(private verbose: boolean = false) {}
+class ServerLogger implements Logger {
+  constructor(private verbose: boolean = false, private json: boolean = false) {}
+
+  debug(message: string, data?: unknown): void {
+    if (this.verbose) {
+      this.write('debug', chalk.gray, message, data);
+    }
+  }
 
   info(message: string, data?: unknown): void {
     if (this.verbose) {
-      const dataStr = data ? ` ${JSON.stringify(data)}` : '';
-      console.error(chalk.blue(`[INFO] ${message}${dataStr}`));
+      this.write('info', chalk.blue, message, data);
     }
   }
 
   warn(message: string, data?: unknown): void {
-    const dataStr = data ? ` ${JSON.stringify(data)}` : '';
-    console.error(chalk.yellow(`[WARN] ${message}${dataStr}`));
+    this.write('warn', chalk.yellow, message, data);
   }
 
   error(message: string, data?: unknown): void {
-    const dataStr = data ? ` ${JSON.stringify(data)}` : '';
-    console.error(chalk.red(`[ERROR] ${message}${dataStr}`));
+    this.write('error', chalk.red, message, data);
   }
 
   ready(message: string): void {
-    console.error(chalk.green(`[READY] ${message}`));
+    this.write('ready', chalk.green, message);
+  }
+
+  private write(level: string, color: (text: string) => string, message: string, data?: unknown): void {
+    if (this.json) {
+      console.error(JSON.stringify(data ? { level, message, data } : { level, message }));
+      return;
+    }
+    const dataStr = data ? ` ${JSON.stringify(data)}` : '';
+    console.error(color(`[${level.toUpperCase()}] ${message}${dataStr}`));
   }
 }
 
@@ -56,7 +78,7 @@ class ServerLogger {
  * Start MCP Server in standalone mode
  */
 async function startServer(options: ServerOptions): Promise<void> {
-  const logger = new ServerLogger(options.verbose);
+  const logger = new ServerLogger(options.verbose, options.json);
   const projectRoot = process.cwd();
 
   logger.info('Starting MYCELIUM MCP Server...', { projectRoot });
@@ -65,11 +87,9 @@ async function startServer(options: ServerOptions): Promise<void> {
   const configPath = join(projectRoot, options.config);
   logger.info(`Loading config from: ${configPath}`);
 
-  let config: { mcpServers?: Record<string, unknown> } = {};
+  let config: DesktopConfig = { mcpServers: {} };
   try {
-    await access(configPath);
-    const configContent = await readFile(configPath, 'utf-8');
-    config = JSON.parse(configContent);
+    config = await loadDesktopConfigFromFile(configPath);
   } catch (error) {
     logger.warn(`Config file not found or invalid: ${configPath}`);
   }
@@ -89,27 +109,28 @@ async function startServer(options: ServerOptions): Promise<void> {
   );
 
   // Create Router Core
-  const routerCore = createMyceliumCore(logger as any, {
+  const routerCore = createMyceliumCore(logger, {
     rolesDir: join(projectRoot, 'roles'),
     cwd: projectRoot,
   });
 
   // Add backend servers from config
-  if (config.mcpServers) {
-    for (const [name, serverConfig] of Object.entries(config.mcpServers)) {
-      logger.info(`Adding backend server: ${name}`);
-      await routerCore.addServer(name, serverConfig as any);
-    }
-    logger.info(`Loaded ${Object.keys(config.mcpServers).length} backend servers`);
+  for (const [name, serverConfig] of Object.entries(config.mcpServers)) {
+    logger.info(`Adding backend server: ${name}`);
+    await routerCore.addServer(name, serverConfig);
   }
+  logger.info(`Loaded ${Object.keys(config.mcpServers).length} backend servers`);
 
   // Initialize router
   await routerCore.initialize();
 
   // Start backend servers
   logger.info('Starting backend servers...');
-  await routerCore.startServers();
-  logger.info('Backend servers started');
+  const startReport = await routerCore.startServers();
+  for (const failure of startReport.failed) {
+    logger.warn(`Backend server '${failure.name}' failed to start (its tools are hidden): ${failure.error}`);
+  }
+  logger.info(`Backend servers started: ${startReport.started.length}, failed: ${startReport.failed.length}`);
 
   // Load roles from skills server
   logger.info('Loading roles from skills...');
@@ -186,15 +207,7 @@ function setupRequestHandlers(
     logger.info(`Tool call: ${name}`);
 
     // System tools (always allowed)
-    const SYSTEM_TOOLS = [
-      'mycelium-router__get_context',
-      'mycelium-router__list_roles',
-      'mycelium-router__set_active_skills',
-      'mycelium-router__get_active_skills',
-      'mycelium-router__list_skills'
-    ];
-    const isSystemTool = SYSTEM_TOOLS.includes(name) ||
-      SYSTEM_TOOLS.some(t => name.endsWith(`__${t.replace('mycelium-router__', '')}`));
+    const isSystemTool = routerCore.getSystemTools().isSystemTool(name);
 
     // Check access
     if (!isSystemTool) {
@@ -353,17 +366,123 @@ function setupGracefulShutdown(server: Server, logger: ServerLogger): void {
   process.on('SIGTERM', () => shutdown('SIGTERM'));
 }
 
+// ============================================================================
+// mycelium server status - Backend server states
+// ============================================================================
+
+interface StatusOptions extends OutputOptions {
+  config: string;
+  start?: boolean;
+  timeout: string;
+  verbose?: boolean;
+}
+
+/**
+ * State of a single backend server, for display
+ */
+export interface ServerStatusRow {
+  name: string;
+  state: ServerState;
+}
+
+const STATE_LABELS: Record<ServerState, string> = {
+  not_started: chalk.gray('not started'),
+  starting: chalk.yellow('starting'),
+  running: chalk.green('running'),
+  crashed: chalk.red('crashed'),
+};
+
+/**
+ * Collect backend server states sorted by name
+ */
+export function collectServerStatus(router: Pick<StdioRouter, 'getServerStates'>): ServerStatusRow[] {
+  return Object.entries(router.getServerStates())
+    .map(([name, state]) => ({ name, state }))
+    .sort((a, b) => a.name.localeCompare(b.name));
+}
+
+/**
+ * Render server states as an aligned table
+ */
+export function formatServerStatus(rows: ServerStatusRow[]): string {
+  if (rows.length === 0) {
+    return chalk.gray('No backend servers configured');
+  }
+
+  const width = Math.max('SERVER'.length, ...rows.map(row => row.name.length));
+  const lines = [chalk.bold(`${'SERVER'.padEnd(width)}  STATE`)];
+  for (const row of rows) {
+    lines.push(`${row.name.padEnd(width)}  ${STATE_LABELS[row.state]}`);
+  }
+  return lines.join('\n');
+}
+
+/**
+ * Report the state of each configured backend server
+ * Nothing is spawned unless `start` is set; then each backend is started, reported on
+ * once startup settles or the timeout passes (slow ones show as starting), and stopped again
+ */
+async function reportServerStatus(options: StatusOptions): Promise<void> {
+  const logger = new ServerLogger(options.verbose, options.json);
+  const config = await loadDesktopConfigFromFile(join(process.cwd(), options.config));
+
+  const router = new StdioRouter(logger, { cwd: process.cwd() });
+  router.loadServersFromDesktopConfig(config);
+
+  try {
+    if (options.start) {
+      const timeoutMs = parseInt(options.timeout, 10);
+      if (!Number.isInteger(timeoutMs) || timeoutMs < 0) {
+        throw new Error(`Invalid --timeout: ${options.timeout}`);
+      }
+      let timer: NodeJS.Timeout | undefined;
+      await Promise.race([
+        router.startServers(),
+        new Promise<void>(resolve => { timer = setTimeout(resolve, timeoutMs); }),
+      ]);
+      clearTimeout(timer);
+    }
+
+    const rows = collectServerStatus(router);
+    printResult(options, rows, formatServerStatus);
+
+    if (options.start && rows.some(row => row.state !== 'running')) {
+      process.exitCode = 1;
+    }
+  } finally {
+    await router.stopServers();
+  }
+}
+
+const statusCommand = new Command('status')
+  .description('Report the state of each configured backend server')
+  .option('-c, --config <path>', 'Config file path', 'config.json')
+  .option('--start', 'Start each backend to check it comes up, then stop it again')
+  .option('--timeout <ms>', 'With --start, report after this long even if servers are still starting', '30000')
+  .option('--json', 'Output the server states as JSON')
+  .option('-v, --verbose', 'Enable verbose logging')
+  .action(async (options: StatusOptions) => {
+    try {
+      await reportServerStatus(options);
+    } catch (error) {
+      exitWithError(options, error);
+    }
+  });
+
 // Command definition
 export const serverCommand = new Command('server')
   .description('Start MYCELIUM as a standalone MCP server')
   .option('-c, --config <path>', 'Config file path', 'config.json')
   .option('-r, --role <role>', 'Default role for the server')
   .option('-v, --verbose', 'Enable verbose logging')
+  .option('--json', 'Write log lines to stderr as JSON')
+  // Options after `status` belong to the subcommand, not the server itself
+  .enablePositionalOptions()
+  .addCommand(statusCommand)
   .action(async (options: ServerOptions) => {
     try {
       await startServer(options);
     } catch (error) {
-      console.error(chalk.red('Fatal error:'), error);
-      process.exit(1);
+      exitWithError(options, error, 'Fatal error:');
     }
   });
@@ -0,0 +1,154 @@
+// ============================================================================
+// mycelium skill - Manage skill manifests
+// ============================================================================
+
+import { Command } from 'commander';
+import { resolve } from 'path';
+import chalk from 'chalk';
+import { input } from '@inquirer/prompts';
+import {
+  addSkillToManifest,
+  loadSkillManifest,
+  validateSkillManifest,
+  summarizeSkills,
+  type SkillSummary,
+  type ManifestIssue
+} from '../lib/manifest.js';
+import { exitWithError, printResult, type OutputOptions } from '../lib/output.js';
+
+/**
+ * Render skill summaries for terminal output
+ */
+export function formatSkillList(skills: SkillSummary[]): string {
+  if (skills.length === 0) {
+    return chalk.gray('No skills defined');
+  }
+
+  const lines: string[] = [];
+  for (const skill of skills) {
+    lines.push(`${chalk.cyan(skill.id)}  ${chalk.gray(skill.description)}`);
+    lines.push(`    roles: ${skill.roles.join(', ') || chalk.gray('(none)')}`);
+    lines.push(`    tools: ${skill.tools.length}`);
+  }
+  return lines.join('\n');
+}
+
+/**
+ * Result of `skill validate`
+ */
+export interface SkillValidationResult {
+  valid: boolean;
+  skillCount: number;
+  issues: ManifestIssue[];
+}
+
+/**
+ * Render a validation result for terminal output
+ */
+export function formatValidationResult(result: SkillValidationResult): string {
+  if (result.valid) {
+    return chalk.green(`✓ ${result.skillCount} skills, no issues found`);
+  }
+
+  const lines = [chalk.red(`✗ ${result.issues.length} issue(s) found:`)];
+  for (const issue of result.issues) {
+    lines.push(`  ${chalk.yellow(issue.skillId)}: ${issue.message}`);
+  }
+  return lines.join('\n');
+}
+
+interface AddOptions extends OutputOptions {
+  name?: string;
+  description?: string;
+  role: string[];
+  tool: string[];
+}
+
+function collect(value: string, previous: string[]): string[] {
+  return [...previous, value];
+}
+
+function splitList(value: string): string[] {
+  return value.split(',').map(item => item.trim()).filter(Boolean);
+}
+
+const addCommand = new Command('add')
+  .description('Add a skill to a skill manifest file')
+  .argument('<file>', 'Skill manifest (JSON or YAML); created if missing')
+  .argument('<id>', 'Skill ID')
+  .option('-n, --name <displayName>', 'Display name')
+  .option('-d, --description <text>', 'Skill description')
+  .option('-r, --role <id>', 'Allowed role (repeatable)', collect, [])
+  .option('-t, --tool <name>', 'Allowed tool, server__tool (repeatable)', collect, [])
+  .option('--json', 'Output the added skill as JSON')
+  .action(async (file: string, id: string, options: AddOptions) => {
+    try {
+      const interactive = Boolean(process.stdin.isTTY) && !options.json;
+      let { name, description, role: roles, tool: tools } = options;
+
+      if (interactive) {
+        name ??= await input({ message: 'Display name:', default: id });
+        description ??= await input({ message: 'Description:' });
+        if (roles.length === 0) {
+          roles = splitList(await input({ message: 'Allowed roles (comma-separated):' }));
+        }
+        if (tools.length === 0) {
+          tools = splitList(await input({ message: 'Allowed tools (comma-separated, server__tool):' }));
+        }
+      }
+
+      const manifest = await addSkillToManifest(resolve(file), {
+        id,
+        displayName: name ?? id,
+        description: description ?? '',
+        allowedRoles: roles,
+        allowedTools: tools
+      });
+
+      printResult(options, { id, file, skillCount: manifest.skills.length }, added =>
+        chalk.green(`✓ Added skill '${added.id}' (${added.skillCount} skills in ${added.file})`));
+    } catch (error) {
+      exitWithError(options, error);
+    }
+  });
+
+const listCommand = new Command('list')
+  .description('List the skills in a skill manifest')
+  .argument('<file>', 'Skill manifest (JSON or YAML)')
+  .option('--json', 'Output the skills as JSON')
+  .action(async (file: string, options: OutputOptions) => {
+    try {
+      const manifest = await loadSkillManifest(resolve(file));
+      printResult(options, summarizeSkills(manifest), formatSkillList);
+    } catch (error) {
+      exitWithError(options, error);
+    }
+  });
+
+const validateCommand = new Command('validate')
+  .description('Validate a skill manifest file')
+  .argument('<file>', 'Skill manifest (JSON or YAML)')
+  .option('--json', 'Output the validation result as JSON')
+  .action(async (file: string, options: OutputOptions) => {
+    try {
+      const manifest = await loadSkillManifest(resolve(file));
+      const issues = validateSkillManifest(manifest);
+      printResult(options, {
+        valid: issues.length === 0,
+        skillCount: manifest.skills.length,
+        issues
+      }, formatValidationResult);
+
+      if (issues.length > 0) {
+        process.exit(1);
+      }
+    } catch (error) {
+      exitWithError(options, error);
+    }
+  });
+
+export const skillCommand = new Command('skill')
+  .description('Manage skill manifests')
+  .addCommand(addCommand)
+  .addCommand(listCommand)
+  .addCommand(validateCommand);
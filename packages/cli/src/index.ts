@@ -4,12 +4,17 @@
  *
  * Usage:
  *   mycelium server       - Start as standalone MCP server (for Claude Desktop/Cursor)
+ *   mycelium server status - Report the state of each configured backend server
  *   mycelium client       - Connect to running MCP server (thin client)
+ *   mycelium policy       - Inspect skill-based role policies
+ *   mycelium skill        - Manage skill manifests (add, list, validate)
  */
 
 import { Command } from 'commander';
 import { serverCommand } from './commands/server.js';
 import { clientCommand } from './commands/client.js';
+import { policyCommand } from './commands/policy.js';
+import { skillCommand } from './commands/skill.js';
 
 const program = new Command();
 
@@ -21,6 +26,8 @@ program
 // Register subcommands
 program.addCommand(serverCommand);  // MCP server standalone mode
 program.addCommand(clientCommand);  // MCP client thin mode
+program.addCommand(policyCommand);  // Policy inspection
+program.addCommand(skillCommand);   // Skill manifest management
 
 // Default action: show help
 program.action(() => {
@@ -0,0 +1,15 @@
+// ============================================================================
+// Mycelium CLI - Loggers
+// ============================================================================
+
+import type { Logger } from '@mycelium/shared';
+
+/**
+ * Logger that discards everything, for commands that report results themselves
+ */
+export const silentLogger: Logger = {
+  debug: () => {},
+  info: () => {},
+  warn: () => {},
+  error: () => {}
+};
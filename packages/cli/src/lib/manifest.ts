@@ -0,0 +1,163 @@
+// ============================================================================
+// Mycelium CLI - Skill Manifest Loading
+// ============================================================================
+
+import { promises as fs } from 'fs';
+import { migrateSkillManifest, type BaseSkillDefinition, type SkillManifest } from '@mycelium/shared';
+
+/**
+ * Parse skill manifest content (JSON or YAML)
+ * Accepts either a full manifest object or a bare list of skills;
+ * older schema versions are migrated to the current shape
+ * @throws Error naming the file if the content is not a skill manifest
+ */
+export async function parseSkillManifest(content: string, filePath: string): Promise<SkillManifest> {
+  let raw: unknown;
+  if (filePath.endsWith('.yaml') || filePath.endsWith('.yml')) {
+    const yaml = await import('yaml');
+    raw = yaml.parse(content);
+  } else {
+    raw = JSON.parse(content);
+  }
+
+  try {
+    return migrateSkillManifest(raw);
+  } catch (error) {
+    const message = error instanceof Error ? error.message : String(error);
+    throw new Error(`${filePath}: ${message}`, { cause: error });
+  }
+}
+
+/**
+ * Load a skill manifest from a JSON or YAML file
+ */
+export async function loadSkillManifest(filePath: string): Promise<SkillManifest> {
+  const content = await fs.readFile(filePath, 'utf-8');
+  return parseSkillManifest(content, filePath);
+}
+
+/**
+ * Serialize a skill manifest as JSON or YAML (chosen by file extension)
+ */
+export async function serializeSkillManifest(manifest: SkillManifest, filePath: string): Promise<string> {
+  const data = { ...manifest, generatedAt: manifest.generatedAt.toISOString() };
+  if (filePath.endsWith('.yaml') || filePath.endsWith('.yml')) {
+    const yaml = await import('yaml');
+    return yaml.stringify(data);
+  }
+  return JSON.stringify(data, null, 2) + '\n';
+}
+
+/**
+ * Append a skill to a manifest file, creating the file if it does not exist
+ * @throws Error if the skill ID already exists or the new skill is invalid
+ */
+export async function addSkillToManifest(filePath: string, skill: BaseSkillDefinition): Promise<SkillManifest> {
+  let manifest: SkillManifest;
+  try {
+    manifest = await loadSkillManifest(filePath);
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code !== 'ENOENT') throw error;
+    manifest = { skills: [], version: '1.0.0', generatedAt: new Date() };
+  }
+
+  if (manifest.skills.some(existing => existing.id === skill.id)) {
+    throw new Error(`Skill '${skill.id}' already exists in ${filePath}`);
+  }
+
+  const issues = validateSkillManifest({ ...manifest, skills: [skill] });
+  if (issues.length > 0) {
+    throw new Error(`Invalid skill '${skill.id}': ${issues.map(issue => issue.message).join('; ')}`);
+  }
+
+  const updated: SkillManifest = { ...manifest, skills: [...manifest.skills, skill], generatedAt: new Date() };
+  await fs.writeFile(filePath, await serializeSkillManifest(updated, filePath), 'utf-8');
+  return updated;
+}
+
+// ============================================================================
+// Summaries
+// ============================================================================
+
+/**
+ * Stable per-skill summary used by `skill list` output
+ */
+export interface SkillSummary {
+  id: string;
+  displayName: string;
+  description: string;
+  roles: string[];
+  tools: string[];
+}
+
+/**
+ * Summarize the skills in a manifest, sorted by ID
+ */
+export function summarizeSkills(manifest: SkillManifest): SkillSummary[] {
+  return manifest.skills
+    .map(skill => ({
+      id: skill.id,
+      displayName: skill.displayName ?? skill.id,
+      description: skill.description ?? '',
+      roles: [...skill.allowedRoles],
+      tools: [...skill.allowedTools]
+    }))
+    .sort((a, b) => a.id.localeCompare(b.id));
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+/**
+ * A problem found in a skill manifest
+ */
+export interface ManifestIssue {
+  /** Skill the issue applies to */
+  skillId: string;
+
+  /** Human-readable description */
+  message: string;
+}
+
+/**
+ * Check whether a tool name follows the server__tool format (wildcards allowed)
+ */
+export function isValidToolName(tool: string): boolean {
+  if (tool === '*') return true;
+  const separator = tool.indexOf('__');
+  return separator > 0 && separator + 2 < tool.length;
+}
+
+/**
+ * Validate a skill manifest
+ * Flags duplicate skill IDs, skills without roles, malformed tool names,
+ * and team memory grants without team roles
+ */
+export function validateSkillManifest(manifest: SkillManifest): ManifestIssue[] {
+  const issues: ManifestIssue[] = [];
+  const seen = new Set<string>();
+
+  for (const skill of manifest.skills) {
+    if (seen.has(skill.id)) {
+      issues.push({ skillId: skill.id, message: 'Duplicate skill ID' });
+    }
+    seen.add(skill.id);
+
+    if (skill.allowedRoles.length === 0) {
+      issues.push({ skillId: skill.id, message: 'allowedRoles is empty' });
+    }
+
+    for (const tool of skill.allowedTools) {
+      if (!isValidToolName(tool)) {
+        issues.push({ skillId: skill.id, message: `Tool '${tool}' does not follow the server__tool format` });
+      }
+    }
+
+    if (skill.grants?.memory === 'team' && !skill.grants.memoryTeamRoles?.length) {
+      issues.push({ skillId: skill.id, message: "Memory grant 'team' requires memoryTeamRoles" });
+    }
+  }
+
+  return issues;
+}
@@ -0,0 +1,39 @@
+// ============================================================================
+// Mycelium CLI - Command Output
+// ============================================================================
+
+import chalk from 'chalk';
+
+/**
+ * Options shared by every command that accepts --json
+ */
+export interface OutputOptions {
+  json?: boolean;
+}
+
+/**
+ * Print a command result: pretty-printed JSON with --json, otherwise the rendered text
+ */
+export function printResult<T>(options: OutputOptions, data: T, render: (data: T) => string): void {
+  console.log(options.json ? JSON.stringify(data, null, 2) : render(data));
+}
+
+/**
+ * Print a command failure to stderr; with --json it is written as {"error": message}
+ */
+export function printError(options: OutputOptions, error: unknown, label: string = 'Error:'): void {
+  const message = error instanceof Error ? error.message : String(error);
+  if (options.json) {
+    console.error(JSON.stringify({ error: message }));
+  } else {
+    console.error(chalk.red(label), message);
+  }
+}
+
+/**
+ * Print a command failure and exit with status 1
+ */
+export function exitWithError(options: OutputOptions, error: unknown, label?: string): never {
+  printError(options, error, label);
+  process.exit(1);
+}
@@ -0,0 +1,51 @@
+// ============================================================================
+// Mycelium CLI - Policy Check
+// ============================================================================
+
+import { RoleManager, createRoleManager, type ToolAccessTrace } from '@mycelium/core';
+import type { SkillManifest, VisibilityReason } from '@mycelium/shared';
+import { silentLogger } from './logger.js';
+
+/**
+ * Access decision for one role
+ */
+export interface RoleToolAccess {
+  roleId: string;
+  allowed: boolean;
+  reason: VisibilityReason;
+}
+
+/**
+ * Load a skill manifest into a fresh RoleManager
+ */
+export async function loadRoleManager(manifest: SkillManifest): Promise<RoleManager> {
+  const roleManager = createRoleManager(silentLogger);
+  await roleManager.loadFromSkillManifest(manifest);
+  return roleManager;
+}
+
+/**
+ * Decide access to a tool for every role in a manifest
+ */
+export async function checkToolAcrossRoles(manifest: SkillManifest, toolName: string): Promise<RoleToolAccess[]> {
+  const roleManager = await loadRoleManager(manifest);
+  const serverName = RoleManager.extractServerFromTool(toolName) ?? '';
+
+  return roleManager.getRoleIds().sort().map(roleId => ({
+    roleId,
+    ...roleManager.getToolAccessDecision(roleId, toolName, serverName)
+  }));
+}
+
+/**
+ * Explain a tool decision for one role and the rule that decided it
+ * Roles built from a skill manifest never inherit, so their chain is just the role;
+ * roles registered with an `inherits` link also show the ancestor that contributed the rule
+ */
+export function traceToolForRole(roleManager: RoleManager, roleId: string, toolName: string): ToolAccessTrace {
+  if (!roleManager.hasRole(roleId)) {
+    throw new Error(`Role '${roleId}' not found. Available roles: ${roleManager.getRoleIds().sort().join(', ')}`);
+  }
+  const serverName = RoleManager.extractServerFromTool(toolName) ?? '';
+  return roleManager.traceToolAccess(roleId, toolName, serverName);
+}
@@ -0,0 +1,145 @@
+// ============================================================================
+// Mycelium CLI - Policy Diff
+// ============================================================================
+
+import { createRoleManager } from '@mycelium/core';
+import type { RoleManifest, SkillManifest } from '@mycelium/shared';
+import { silentLogger } from './logger.js';
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/**
+ * A tool that stays available to a role but is granted by different skills
+ */
+export interface MovedTool {
+  tool: string;
+  fromSkills: string[];
+  toSkills: string[];
+}
+
+/**
+ * Capability changes for a single role
+ * - 'added': role only exists in the new manifest
+ * - 'removed': role only exists in the old manifest
+ * - 'changed': role exists in both with different skills or tools
+ */
+export interface RoleDiff {
+  roleId: string;
+  status: 'added' | 'removed' | 'changed';
+  addedSkills: string[];
+  removedSkills: string[];
+  addedTools: string[];
+  removedTools: string[];
+  movedTools: MovedTool[];
+}
+
+/**
+ * Result of comparing two skill manifests
+ */
+export interface PolicyDiff {
+  roles: RoleDiff[];
+  unchangedRoles: string[];
+}
+
+// ============================================================================
+// Diff
+// ============================================================================
+
+/**
+ * Map each role's tools to the skills granting them
+ * The '*' role is skipped, as generateRoleManifest does not support it
+ */
+function toolSourcesByRole(manifest: SkillManifest): Map<string, Map<string, string[]>> {
+  const result = new Map<string, Map<string, string[]>>();
+
+  for (const skill of manifest.skills) {
+    for (const roleId of skill.allowedRoles) {
+      if (roleId === '*') continue;
+      let sources = result.get(roleId);
+      if (!sources) {
+        sources = new Map();
+        result.set(roleId, sources);
+      }
+      for (const tool of skill.allowedTools) {
+        const skills = sources.get(tool) ?? [];
+        if (!skills.includes(skill.id)) skills.push(skill.id);
+        sources.set(tool, skills);
+      }
+    }
+  }
+
+  return result;
+}
+
+function difference(a: string[], b: string[]): string[] {
+  const other = new Set(b);
+  return a.filter(item => !other.has(item)).sort();
+}
+
+function sameMembers(a: string[], b: string[]): boolean {
+  return a.length === b.length && difference(a, b).length === 0;
+}
+
+/**
+ * Compare the roles generated from two skill manifests
+ */
+export function diffSkillManifests(before: SkillManifest, after: SkillManifest): PolicyDiff {
+  const roleManager = createRoleManager(silentLogger);
+  const beforeRoles: RoleManifest = roleManager.generateRoleManifest(before);
+  const afterRoles: RoleManifest = roleManager.generateRoleManifest(after);
+  const beforeSources = toolSourcesByRole(before);
+  const afterSources = toolSourcesByRole(after);
+
+  const roleIds = [...new Set([
+    ...Object.keys(beforeRoles.roles),
+    ...Object.keys(afterRoles.roles)
+  ])].sort();
+
+  const roles: RoleDiff[] = [];
+  const unchangedRoles: string[] = [];
+
+  for (const roleId of roleIds) {
+    const oldRole = beforeRoles.roles[roleId];
+    const newRole = afterRoles.roles[roleId];
+    const oldSkills = oldRole?.skills ?? [];
+    const newSkills = newRole?.skills ?? [];
+    const oldTools = oldRole?.tools ?? [];
+    const newTools = newRole?.tools ?? [];
+
+    const movedTools: MovedTool[] = [];
+    if (oldRole && newRole) {
+      for (const tool of oldTools.filter(t => newTools.includes(t)).sort()) {
+        const fromSkills = beforeSources.get(roleId)?.get(tool) ?? [];
+        const toSkills = afterSources.get(roleId)?.get(tool) ?? [];
+        if (!sameMembers(fromSkills, toSkills)) {
+          movedTools.push({ tool, fromSkills: [...fromSkills].sort(), toSkills: [...toSkills].sort() });
+        }
+      }
+    }
+
+    const diff: RoleDiff = {
+      roleId,
+      status: !oldRole ? 'added' : !newRole ? 'removed' : 'changed',
+      addedSkills: difference(newSkills, oldSkills),
+      removedSkills: difference(oldSkills, newSkills),
+      addedTools: difference(newTools, oldTools),
+      removedTools: difference(oldTools, newTools),
+      movedTools
+    };
+
+    const isUnchanged = diff.status === 'changed' &&
+      diff.addedSkills.length === 0 && diff.removedSkills.length === 0 &&
+      diff.addedTools.length === 0 && diff.removedTools.length === 0 &&
+      diff.movedTools.length === 0;
+
+    if (isUnchanged) {
+      unchangedRoles.push(roleId);
+    } else {
+      roles.push(diff);
+    }
+  }
+
+  return { roles, unchangedRoles };
+}
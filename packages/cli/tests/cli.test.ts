@@ -45,6 +45,14 @@ describe('MYCELIUM CLI', () => {
       expect(output).toContain('--config');
       expect(output).toContain('--role');
       expect(output).toContain('--verbose');
+      expect(output).toContain('--json');
+    });
+
+    it('should show server status help', () => {
+      const output = runCli('server status --help');
+      expect(output).toContain('Report the state of each configured backend server');
+      expect(output).toContain('--start');
+      expect(output).toContain('--json');
     });
   });
 
@@ -54,6 +62,40 @@ describe('MYCELIUM CLI', () => {
       expect(output).toContain('Connect to a Mycelium MCP server');
       expect(output).toContain('--config');
       expect(output).toContain('--role');
+      expect(output).toContain('--json');
+    });
+  });
+
+  describe('mycelium policy diff', () => {
+    it('should show policy diff help', () => {
+      const output = runCli('policy diff --help');
+      expect(output).toContain('Compare role capabilities');
+      expect(output).toContain('--json');
+    });
+  });
+
+  describe('mycelium skill validate', () => {
+    it('should show skill validate help', () => {
+      const output = runCli('skill validate --help');
+      expect(output).toContain('Validate a skill manifest file');
+      expect(output).toContain('--json');
+    });
+  });
+
+  describe('mycelium skill add', () => {
+    it('should show skill add help', () => {
+      const output = runCli('skill add --help');
+      expect(output).toContain('Add a skill to a skill manifest file');
+      expect(output).toContain('--role');
+      expect(output).toContain('--tool');
+    });
+  });
+
+  describe('mycelium skill list', () => {
+    it('should show skill list help', () => {
+      const output = runCli('skill list --help');
+      expect(output).toContain('List the skills in a skill manifest');
+      expect(output).toContain('--json');
     });
   });
 });
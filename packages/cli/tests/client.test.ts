@@ -0,0 +1,113 @@
+// ============================================================================
+// Client REPL Tests
+// ============================================================================
+
+import { describe, it, expect, vi, beforeEach, afterEach } from 'vitest';
+import { MyceliumClient, type ToolClient } from '../src/commands/client.js';
+
+const ROLE_TOOLS: Record<string, string[]> = {
+  guest: ['filesystem__read_file'],
+  developer: ['filesystem__read_file', 'filesystem__write_file', 'git__commit']
+};
+
+/**
+ * Fake MCP client backed by a fixed role/tool table
+ */
+function createFakeClient(): ToolClient {
+  let currentRole = 'guest';
+
+  const text = (value: string, isError = false) => ({
+    content: [{ type: 'text', text: value }],
+    isError
+  });
+
+  return {
+    callTool: vi.fn(async ({ name, arguments: args }: { name: string; arguments?: Record<string, unknown> }) => {
+      if (name === 'mycelium-router__set_role') {
+        const role = args?.role as string;
+        if (!ROLE_TOOLS[role]) {
+          return text(`Error: Role '${role}' not found`, true);
+        }
+        currentRole = role;
+        return text(JSON.stringify({ success: true, role: { id: role }, toolCount: ROLE_TOOLS[role].length }));
+      }
+      if (name === 'mycelium-router__list_roles') {
+        return text(JSON.stringify({
+          roles: Object.keys(ROLE_TOOLS).map(id => ({
+            id,
+            description: `${id} role`,
+            toolCount: ROLE_TOOLS[id].length,
+            isCurrent: id === currentRole
+          })),
+          currentRole
+        }));
+      }
+      return text('{}');
+    }),
+    listTools: vi.fn(async () => ({
+      tools: ROLE_TOOLS[currentRole].map(name => ({ name, inputSchema: { type: 'object' as const } }))
+    })),
+    close: vi.fn(async () => {})
+  } as unknown as ToolClient;
+}
+
+describe('MyceliumClient REPL commands', () => {
+  let output: string[];
+  let client: MyceliumClient;
+
+  beforeEach(() => {
+    output = [];
+    vi.spyOn(console, 'log').mockImplementation((...args: unknown[]) => {
+      output.push(args.join(' '));
+    });
+    client = new MyceliumClient({ config: 'config.json' }, createFakeClient());
+  });
+
+  afterEach(() => {
+    vi.restoreAllMocks();
+  });
+
+  it('should switch role and print the new tool set', async () => {
+    for (const line of ['/role developer', '/tools']) {
+      expect(await client.handleCommand(line)).toBe(true);
+    }
+
+    const printed = output.join('\n');
+    expect(printed).toContain('Role: developer (3 tools)');
+    expect(printed).toContain('filesystem__write_file');
+    expect(printed).toContain('git__commit');
+    expect(printed).toContain('Total: 3 tools');
+  });
+
+  it('should print a clear error for an unknown role', async () => {
+    await client.handleCommand('/role ghost');
+
+    expect(output.join('\n')).toContain("Failed to switch to role 'ghost': Role 'ghost' not found");
+  });
+
+  it('should list roles with the current one marked', async () => {
+    await client.handleCommand('/roles');
+
+    const printed = output.join('\n');
+    expect(printed).toContain('guest (1 tools)');
+    expect(printed).toContain('developer (3 tools)');
+  });
+
+  it('should return false on /exit', async () => {
+    expect(await client.handleCommand('/exit')).toBe(false);
+  });
+
+  it('should print results and failures as JSON with --json', async () => {
+    client = new MyceliumClient({ config: 'config.json', json: true }, createFakeClient());
+
+    await client.handleCommand('/role developer');
+    await client.handleCommand('/roles');
+    await client.handleCommand('/role ghost');
+
+    const [switched, roles, failed] = output.map(line => JSON.parse(line));
+    expect(switched).toMatchObject({ success: true, toolCount: 3 });
+    expect(roles.map((role: { id: string }) => role.id)).toEqual(['guest', 'developer']);
+    expect(roles.find((role: { isCurrent?: boolean }) => role.isCurrent).id).toBe('developer');
+    expect(failed).toEqual({ error: "Failed to switch to role 'ghost': Role 'ghost' not found" });
+  });
+});
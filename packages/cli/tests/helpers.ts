@@ -0,0 +1,12 @@
+// ============================================================================
+// Shared fixtures for CLI tests
+// ============================================================================
+
+import type { BaseSkillDefinition, Logger, SkillManifest } from '@mycelium/shared';
+
+// Silent test logger
+export const testLogger: Logger = { debug: () => {}, info: () => {}, warn: () => {}, error: () => {} };
+
+export function createManifest(skills: BaseSkillDefinition[]): SkillManifest {
+  return { skills, version: '1.0.0', generatedAt: new Date() };
+}
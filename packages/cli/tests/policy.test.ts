@@ -0,0 +1,194 @@
+// ============================================================================
+// Policy Diff Tests
+// ============================================================================
+
+import { describe, it, expect } from 'vitest';
+import { diffSkillManifests } from '../src/lib/policy-diff.js';
+import { parseSkillManifest } from '../src/lib/manifest.js';
+import { checkToolAcrossRoles, loadRoleManager, traceToolForRole } from '../src/lib/policy-check.js';
+import { formatAccessTrace } from '../src/commands/policy.js';
+import { createRoleManager } from '@mycelium/core';
+import type { BaseSkillDefinition, Role } from '@mycelium/shared';
+import { testLogger, createManifest } from './helpers.js';
+
+const readerSkill: BaseSkillDefinition = {
+  id: 'reader',
+  displayName: 'Reader',
+  description: 'Read files',
+  allowedRoles: ['developer'],
+  allowedTools: ['filesystem__read_file']
+};
+
+describe('diffSkillManifests', () => {
+  it('should list a newly granted tool as added', () => {
+    const before = createManifest([readerSkill]);
+    const after = createManifest([
+      { ...readerSkill, allowedTools: ['filesystem__read_file', 'filesystem__write_file'] }
+    ]);
+
+    const diff = diffSkillManifests(before, after);
+
+    expect(diff.roles).toHaveLength(1);
+    expect(diff.roles[0]).toMatchObject({
+      roleId: 'developer',
+      status: 'changed',
+      addedTools: ['filesystem__write_file'],
+      removedTools: []
+    });
+  });
+
+  it('should report roles that exist in only one manifest', () => {
+    const before = createManifest([readerSkill]);
+    const after = createManifest([{ ...readerSkill, allowedRoles: ['reviewer'] }]);
+
+    const diff = diffSkillManifests(before, after);
+
+    expect(diff.roles.map(r => [r.roleId, r.status])).toEqual([
+      ['developer', 'removed'],
+      ['reviewer', 'added']
+    ]);
+    expect(diff.roles[1].addedTools).toEqual(['filesystem__read_file']);
+  });
+
+  it('should report tools that move between skills', () => {
+    const before = createManifest([readerSkill]);
+    const after = createManifest([
+      { ...readerSkill, allowedTools: [] },
+      {
+        id: 'editor',
+        displayName: 'Editor',
+        description: 'Edit files',
+        allowedRoles: ['developer'],
+        allowedTools: ['filesystem__read_file']
+      }
+    ]);
+
+    const diff = diffSkillManifests(before, after);
+
+    expect(diff.roles[0].addedSkills).toEqual(['editor']);
+    expect(diff.roles[0].addedTools).toEqual([]);
+    expect(diff.roles[0].movedTools).toEqual([
+      { tool: 'filesystem__read_file', fromSkills: ['reader'], toSkills: ['editor'] }
+    ]);
+  });
+
+  it('should list roles without changes as unchanged', () => {
+    const diff = diffSkillManifests(createManifest([readerSkill]), createManifest([readerSkill]));
+
+    expect(diff.roles).toEqual([]);
+    expect(diff.unchangedRoles).toEqual(['developer']);
+  });
+
+  it('should ignore the unsupported wildcard role', () => {
+    const before = createManifest([readerSkill]);
+    const after = createManifest([{ ...readerSkill, allowedRoles: ['developer', '*'] }]);
+
+    const diff = diffSkillManifests(before, after);
+
+    expect(diff.roles).toEqual([]);
+    expect(diff.unchangedRoles).toEqual(['developer']);
+  });
+});
+
+describe('parseSkillManifest', () => {
+  it('should accept a bare YAML list of skills', async () => {
+    const manifest = await parseSkillManifest(
+      '- id: reader\n  allowedRoles: [developer]\n  allowedTools: [filesystem__read_file]\n',
+      'skills.yaml'
+    );
+
+    expect(manifest.skills[0].id).toBe('reader');
+    expect(manifest.version).toBe('1.0.0');
+  });
+
+  it('should reject content without skills', async () => {
+    await expect(parseSkillManifest('{}', 'skills.json')).rejects.toThrow("skills.json: Invalid skill manifest: missing 'skills' list");
+  });
+
+  it('should name the file when the schema version is unsupported', async () => {
+    await expect(parseSkillManifest('{"schemaVersion": 99, "skills": []}', 'skills.json'))
+      .rejects.toThrow('skills.json: Unsupported skill manifest schema version 99');
+  });
+});
+
+describe('checkToolAcrossRoles', () => {
+  it('should only allow roles with the wildcard to delete files', async () => {
+    const manifest = createManifest([
+      readerSkill,
+      {
+        id: 'fs-admin',
+        displayName: 'Filesystem Admin',
+        description: 'Full filesystem access',
+        allowedRoles: ['admin'],
+        allowedTools: ['filesystem__*']
+      },
+      {
+        id: 'git',
+        displayName: 'Git',
+        description: 'Git access',
+        allowedRoles: ['reviewer'],
+        allowedTools: ['git__log']
+      }
+    ]);
+
+    const results = await checkToolAcrossRoles(manifest, 'filesystem__delete_file');
+
+    expect(results.filter(r => r.allowed).map(r => r.roleId)).toEqual(['admin']);
+    expect(results).toEqual([
+      { roleId: 'admin', allowed: true, reason: 'allowed_by_permission' },
+      { roleId: 'developer', allowed: false, reason: 'not_in_allow_list' },
+      { roleId: 'reviewer', allowed: false, reason: 'server_not_allowed' }
+    ]);
+  });
+});
+
+describe('traceToolForRole', () => {
+  function createRole(overrides: Partial<Role> & { id: string }): Role {
+    return {
+      name: overrides.id,
+      description: `${overrides.id} role`,
+      allowedServers: ['filesystem'],
+      systemInstruction: '',
+      ...overrides
+    };
+  }
+
+  it('should name the ancestor that granted an inherited permission', () => {
+    const roleManager = createRoleManager(testLogger);
+    roleManager.registerRole(createRole({ id: 'base', toolPermissions: { allowPatterns: ['filesystem__read_*'] } }));
+    roleManager.registerRole(createRole({ id: 'child', inherits: 'base', toolPermissions: { allow: ['filesystem__stat'] } }));
+
+    const trace = traceToolForRole(roleManager, 'child', 'filesystem__read_file');
+    const output = formatAccessTrace('filesystem__read_file', 'child', trace);
+
+    expect(trace).toMatchObject({ allowed: true, chain: ['child', 'base'], sourceRoleId: 'base', rule: 'filesystem__read_*' });
+    expect(output).toContain('child → base');
+    expect(output).toContain('granted by: base (filesystem__read_*)');
+  });
+
+  it('should trace a registered role through a role loaded from a manifest', async () => {
+    const roleManager = await loadRoleManager(createManifest([readerSkill]));
+    roleManager.registerRole(createRole({ id: 'lead', inherits: 'developer' }));
+
+    const trace = traceToolForRole(roleManager, 'lead', 'filesystem__read_file');
+    const output = formatAccessTrace('filesystem__read_file', 'lead', trace);
+
+    expect(trace).toMatchObject({ allowed: true, chain: ['lead', 'developer'], sourceRoleId: 'developer' });
+    expect(output).toContain('lead → developer');
+    expect(output).toContain('granted by: developer');
+  });
+
+  it('should show a one-role chain for a role built from a manifest', async () => {
+    const roleManager = await loadRoleManager(createManifest([readerSkill]));
+
+    const trace = traceToolForRole(roleManager, 'developer', 'filesystem__read_file');
+
+    expect(trace).toMatchObject({ allowed: true, chain: ['developer'], sourceRoleId: 'developer' });
+  });
+
+  it('should reject an unknown role', () => {
+    const roleManager = createRoleManager(testLogger);
+
+    expect(() => traceToolForRole(roleManager, 'ghost', 'filesystem__read_file')).toThrow("Role 'ghost' not found");
+  });
+});
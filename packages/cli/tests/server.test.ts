@@ -0,0 +1,51 @@
+// ============================================================================
+// Server Status Tests
+// ============================================================================
+
+import { describe, it, expect } from 'vitest';
+import { collectServerStatus, formatServerStatus } from '../src/commands/server.js';
+import type { ServerState } from '@mycelium/core';
+
+function stubRouter(states: Record<string, ServerState>) {
+  return { getServerStates: () => states };
+}
+
+describe('collectServerStatus', () => {
+  it('should report each server state sorted by name', () => {
+    const rows = collectServerStatus(stubRouter({
+      sqlite: 'crashed',
+      filesystem: 'running',
+      git: 'not_started',
+      github: 'starting'
+    }));
+
+    expect(rows).toEqual([
+      { name: 'filesystem', state: 'running' },
+      { name: 'git', state: 'not_started' },
+      { name: 'github', state: 'starting' },
+      { name: 'sqlite', state: 'crashed' }
+    ]);
+  });
+});
+
+describe('formatServerStatus', () => {
+  it('should print one aligned row per server', () => {
+    const output = formatServerStatus(collectServerStatus(stubRouter({
+      filesystem: 'running',
+      git: 'not_started',
+      github: 'starting',
+      sqlite: 'crashed'
+    })));
+    const lines = output.split('\n');
+
+    expect(lines).toHaveLength(5);
+    expect(lines[1]).toMatch(/^filesystem\s+.*running/);
+    expect(lines[2]).toMatch(/^git\s+.*not started/);
+    expect(lines[3]).toMatch(/^github\s+.*starting/);
+    expect(lines[4]).toMatch(/^sqlite\s+.*crashed/);
+  });
+
+  it('should say when no servers are configured', () => {
+    expect(formatServerStatus([])).toContain('No backend servers configured');
+  });
+});
@@ -0,0 +1,179 @@
+// ============================================================================
+// Skill Manifest Validation Tests
+// ============================================================================
+
+import { describe, it, expect, vi, beforeEach, afterEach } from 'vitest';
+import { promises as fs } from 'fs';
+import { tmpdir } from 'os';
+import { join } from 'path';
+import { validateSkillManifest, isValidToolName, loadSkillManifest, addSkillToManifest } from '../src/lib/manifest.js';
+import { skillCommand } from '../src/commands/skill.js';
+import type { BaseSkillDefinition } from '@mycelium/shared';
+import { createManifest } from './helpers.js';
+
+function createSkill(overrides: Partial<BaseSkillDefinition> & { id: string }): BaseSkillDefinition {
+  return {
+    displayName: overrides.id,
+    description: `${overrides.id} skill`,
+    allowedRoles: ['developer'],
+    allowedTools: ['filesystem__read_file'],
+    ...overrides
+  };
+}
+
+describe('validateSkillManifest', () => {
+  it('should pass a clean manifest', () => {
+    const manifest = createManifest([
+      createSkill({ id: 'reader' }),
+      createSkill({
+        id: 'lead',
+        allowedTools: ['git__*'],
+        grants: { memory: 'team', memoryTeamRoles: ['developer'] }
+      })
+    ]);
+
+    expect(validateSkillManifest(manifest)).toEqual([]);
+  });
+
+  it('should fail a team grant without team roles', () => {
+    const manifest = createManifest([
+      createSkill({ id: 'lead', grants: { memory: 'team' } })
+    ]);
+
+    expect(validateSkillManifest(manifest)).toEqual([
+      { skillId: 'lead', message: "Memory grant 'team' requires memoryTeamRoles" }
+    ]);
+  });
+
+  it('should flag duplicate ids, empty roles, and malformed tools', () => {
+    const manifest = createManifest([
+      createSkill({ id: 'reader' }),
+      createSkill({ id: 'reader', allowedRoles: [], allowedTools: ['read_file'] })
+    ]);
+
+    expect(validateSkillManifest(manifest).map(issue => issue.message)).toEqual([
+      'Duplicate skill ID',
+      'allowedRoles is empty',
+      "Tool 'read_file' does not follow the server__tool format"
+    ]);
+  });
+});
+
+describe('isValidToolName', () => {
+  it('should accept prefixed names and wildcards', () => {
+    expect(isValidToolName('filesystem__read_file')).toBe(true);
+    expect(isValidToolName('filesystem__*')).toBe(true);
+    expect(isValidToolName('*')).toBe(true);
+  });
+
+  it('should reject names without a server or tool part', () => {
+    expect(isValidToolName('read_file')).toBe(false);
+    expect(isValidToolName('__read_file')).toBe(false);
+    expect(isValidToolName('filesystem__')).toBe(false);
+  });
+});
+
+describe('skill list --json', () => {
+  afterEach(() => {
+    vi.restoreAllMocks();
+  });
+
+  it('should print a JSON array of skill summaries', async () => {
+    const dir = await fs.mkdtemp(join(tmpdir(), 'mycelium-skill-'));
+    const file = join(dir, 'skills.json');
+    await fs.writeFile(file, JSON.stringify({
+      skills: [
+        createSkill({ id: 'writer', allowedRoles: ['developer', 'admin'], allowedTools: ['filesystem__write_file'] }),
+        createSkill({ id: 'reader' })
+      ]
+    }));
+
+    const output: string[] = [];
+    vi.spyOn(console, 'log').mockImplementation((...args: unknown[]) => {
+      output.push(args.join(' '));
+    });
+
+    try {
+      await skillCommand.parseAsync(['list', file, '--json'], { from: 'user' });
+    } finally {
+      await fs.rm(dir, { recursive: true, force: true });
+    }
+
+    const skills = JSON.parse(output.join('\n'));
+    expect(Array.isArray(skills)).toBe(true);
+    expect(skills).toEqual([
+      {
+        id: 'reader',
+        displayName: 'reader',
+        description: 'reader skill',
+        roles: ['developer'],
+        tools: ['filesystem__read_file']
+      },
+      {
+        id: 'writer',
+        displayName: 'writer',
+        description: 'writer skill',
+        roles: ['developer', 'admin'],
+        tools: ['filesystem__write_file']
+      }
+    ]);
+  });
+
+  it('should report a failure as a JSON error object', async () => {
+    const errors: string[] = [];
+    vi.spyOn(console, 'error').mockImplementation((...args: unknown[]) => {
+      errors.push(args.join(' '));
+    });
+    const exit = vi.spyOn(process, 'exit').mockImplementation((() => undefined) as never);
+
+    await skillCommand.parseAsync(['list', join(tmpdir(), 'mycelium-missing.json'), '--json'], { from: 'user' });
+
+    expect(exit).toHaveBeenCalledWith(1);
+    expect(JSON.parse(errors.join('\n'))).toHaveProperty('error');
+  });
+});
+
+describe('skill add', () => {
+  let dir: string;
+
+  beforeEach(async () => {
+    dir = await fs.mkdtemp(join(tmpdir(), 'mycelium-skill-'));
+    vi.spyOn(console, 'log').mockImplementation(() => {});
+  });
+
+  afterEach(async () => {
+    vi.restoreAllMocks();
+    await fs.rm(dir, { recursive: true, force: true });
+  });
+
+  it('should append a skill given by flags to an existing manifest', async () => {
+    const file = join(dir, 'skills.yaml');
+    await fs.writeFile(file, '- id: reader\n  allowedRoles: [developer]\n  allowedTools: [filesystem__read_file]\n');
+
+    await skillCommand.parseAsync([
+      'add', file, 'git-ops',
+      '--name', 'Git Ops',
+      '--description', 'Commit and push',
+      '--role', 'developer', '--role', 'lead',
+      '--tool', 'git__commit', '--tool', 'git__push'
+    ], { from: 'user' });
+
+    const manifest = await loadSkillManifest(file);
+    expect(manifest.skills.map(skill => skill.id)).toEqual(['reader', 'git-ops']);
+    expect(manifest.skills[1]).toMatchObject({
+      displayName: 'Git Ops',
+      description: 'Commit and push',
+      allowedRoles: ['developer', 'lead'],
+      allowedTools: ['git__commit', 'git__push']
+    });
+    expect(validateSkillManifest(manifest)).toEqual([]);
+  });
+
+  it('should reject a duplicate skill id', async () => {
+    const file = join(dir, 'skills.json');
+    await addSkillToManifest(file, createSkill({ id: 'reader' }));
+
+    await expect(addSkillToManifest(file, createSkill({ id: 'reader' })))
+      .rejects.toThrow("Skill 'reader' already exists");
+  });
+});
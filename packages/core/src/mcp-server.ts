@@ -17,6 +17,7 @@ import { dirname, join } from 'path';
 import { fileURLToPath } from 'url';
 import { Logger } from './utils/logger.js';
 import { MyceliumCore, createMyceliumCore, ROUTER_TOOLS } from './router/mycelium-core.js';
+import { loadDesktopConfigFromFile } from './mcp/desktop-config.js';
 
 // Get the directory of this script (works with ES modules)
 const __filename = fileURLToPath(import.meta.url);
@@ -54,17 +55,13 @@ async function main() {
   logger.info(`Loading backend servers from: ${configPath}`);
 
   try {
-    const fs = await import('fs/promises');
-    const configContent = await fs.readFile(configPath, 'utf-8');
-    const config = JSON.parse(configContent);
-
-    if (config.mcpServers) {
-      for (const [name, serverConfig] of Object.entries(config.mcpServers)) {
-        logger.info(`Adding backend server: ${name}`);
-        await routerCore.addServer(name, serverConfig as any);
-      }
-      logger.info(`Loaded ${Object.keys(config.mcpServers).length} backend server configurations`);
+    const config = await loadDesktopConfigFromFile(configPath);
+
+    for (const [name, serverConfig] of Object.entries(config.mcpServers)) {
+      logger.info(`Adding backend server: ${name}`);
+      await routerCore.addServer(name, serverConfig);
     }
+    logger.info(`Loaded ${Object.keys(config.mcpServers).length} backend server configurations`);
   } catch (error) {
     logger.warn(`Failed to load config from ${configPath}:`, error);
   }
@@ -74,8 +71,11 @@ async function main() {
 
   // Start all backend servers AFTER role is set (so filtering works)
   logger.info('Starting all backend servers...');
-  await routerCore.startServers();
-  logger.info('All backend servers started');
+  const startReport = await routerCore.startServers();
+  for (const failure of startReport.failed) {
+    logger.warn(`Backend server '${failure.name}' failed to start: ${failure.error}`);
+  }
+  logger.info(`Backend servers started: ${startReport.started.length}, failed: ${startReport.failed.length}`);
 
   // Load roles from mycelium-skills server
   logger.info('Loading roles from mycelium-skills...');
@@ -144,18 +144,7 @@ async function main() {
     logger.info(`📥 Tool call received: "${name}"`);
 
     // Check tool access (skip for router system tools - always available)
-    const ROUTER_SYSTEM_TOOLS = [
-      'mycelium-router__get_context',
-      'mycelium-router__list_roles',
-      'mycelium-router__set_active_skills',
-      'mycelium-router__get_active_skills',
-      'mycelium-router__list_skills',
-      'mycelium-router__suggest_skills',
-      'mycelium-router__set_role'
-    ];
-    // Also skip check if tool name ends with system tool suffix
-    const isSystemTool = ROUTER_SYSTEM_TOOLS.includes(name) ||
-      ROUTER_SYSTEM_TOOLS.some(t => name.endsWith(`__${t.replace('mycelium-router__', '')}`));
+    const isSystemTool = routerCore.getSystemTools().isSystemTool(name);
     if (!isSystemTool) {
       try {
         routerCore.checkToolAccess(name);
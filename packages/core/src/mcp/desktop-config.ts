@@ -0,0 +1,66 @@
+// ============================================================================
+// MYCELIUM Router - Desktop Config Loading
+// Locates and reads MCP server configuration files
+// ============================================================================
+
+import { promises as fs } from 'fs';
+import { homedir } from 'os';
+import { join } from 'path';
+import { parseDesktopConfig, type DesktopConfig } from '@mycelium/shared';
+
+/**
+ * Candidate config file locations, in lookup order:
+ * 1. $MYCELIUM_CONFIG_PATH
+ * 2. $XDG_CONFIG_HOME/mycelium/config.json (default ~/.config)
+ * 3. The Claude Desktop config for the platform
+ */
+export function getDesktopConfigPaths(
+  env: NodeJS.ProcessEnv = process.env,
+  platform: NodeJS.Platform = process.platform,
+  home: string = homedir()
+): string[] {
+  const paths: string[] = [];
+
+  if (env.MYCELIUM_CONFIG_PATH) {
+    paths.push(env.MYCELIUM_CONFIG_PATH);
+  }
+
+  const configHome = env.XDG_CONFIG_HOME || join(home, '.config');
+  paths.push(join(configHome, 'mycelium', 'config.json'));
+
+  if (platform === 'darwin') {
+    paths.push(join(home, 'Library', 'Application Support', 'Claude', 'claude_desktop_config.json'));
+  } else if (platform === 'win32') {
+    const appData = env.APPDATA || join(home, 'AppData', 'Roaming');
+    paths.push(join(appData, 'Claude', 'claude_desktop_config.json'));
+  } else {
+    paths.push(join(configHome, 'Claude', 'claude_desktop_config.json'));
+  }
+
+  return paths;
+}
+
+/**
+ * Read a desktop config from a JSON file
+ */
+export async function loadDesktopConfigFromFile(filePath: string): Promise<DesktopConfig> {
+  const content = await fs.readFile(filePath, 'utf-8');
+  return parseDesktopConfig(JSON.parse(content));
+}
+
+/**
+ * Load the first desktop config found in the standard locations
+ * @throws Error listing the searched paths if none exist
+ */
+export async function loadDesktopConfig(paths: string[] = getDesktopConfigPaths()): Promise<DesktopConfig> {
+  for (const filePath of paths) {
+    try {
+      await fs.access(filePath);
+    } catch {
+      continue;
+    }
+    return loadDesktopConfigFromFile(filePath);
+  }
+
+  throw new Error(`No desktop config found. Searched: ${paths.join(', ')}`);
+}
@@ -3,7 +3,10 @@
 // ============================================================================
 
 // StdioRouter (merged from @mycelium/gateway)
-export { StdioRouter, type UpstreamServerInfo } from './stdio-router.js';
+export { StdioRouter, type UpstreamServerInfo, type ServerStartReport, type ServerState } from './stdio-router.js';
+
+// Desktop config loading
+export { getDesktopConfigPaths, loadDesktopConfig, loadDesktopConfigFromFile } from './desktop-config.js';
 
 export * from './tool-discovery.js';
 export * from './dynamic-tool-discovery.js';
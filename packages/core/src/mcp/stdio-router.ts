@@ -6,7 +6,13 @@
 import { EventEmitter } from 'events';
 import { spawn, ChildProcess } from 'child_process';
 import type { Logger, MCPServerConfig } from '@mycelium/shared';
-import { LATEST_PROTOCOL_VERSION } from '@modelcontextprotocol/sdk/types.js';
+import {
+  resolveServerEnv,
+  ServerNotStartedError,
+  MalformedResponseError,
+  ToolCallFailedError
+} from '@mycelium/shared';
+import { LATEST_PROTOCOL_VERSION, type CallToolResult } from '@modelcontextprotocol/sdk/types.js';
 
 /** Timeout constants */
 const TIMEOUTS = {
@@ -20,9 +26,30 @@ export interface UpstreamServerInfo {
   config: MCPServerConfig;
   process?: ChildProcess;
   connected: boolean;
+  state: ServerState;
   buffer: string;
 }
 
+/**
+ * Result of a best-effort server startup
+ */
+export interface ServerStartReport {
+  /** Servers that are running after the startup attempt */
+  started: string[];
+
+  /** Servers that failed to start, with the error message */
+  failed: Array<{ name: string; error: string }>;
+}
+
+/**
+ * Lifecycle state of an upstream server
+ * - 'not_started': No process has been spawned
+ * - 'starting': The process is spawned and the MCP handshake is in progress
+ * - 'running': The process is up and completed the MCP handshake
+ * - 'crashed': The process exited or failed to finish initializing
+ */
+export type ServerState = 'not_started' | 'starting' | 'running' | 'crashed';
+
 /**
  * StdioRouter - Manages connections to upstream MCP servers
  * Minimal implementation for skill-based routing
@@ -51,6 +78,7 @@ export class StdioRouter extends EventEmitter {
       name,
       config,
       connected: false,
+      state: 'not_started',
       buffer: '',
     });
     this.logger.debug(`Added server config: ${name}`);
@@ -75,82 +103,129 @@ export class StdioRouter extends EventEmitter {
     }));
   }
 
+  /**
+   * Get the lifecycle state of each configured server
+   */
+  getServerStates(): Record<string, ServerState> {
+    const states: Record<string, ServerState> = {};
+    for (const server of this.upstreamServers.values()) {
+      states[server.name] = server.state;
+    }
+    return states;
+  }
+
   /**
    * Start all configured servers
    */
-  async startServers(): Promise<void> {
-    const startPromises = Array.from(this.upstreamServers.keys()).map(
-      name => this.startServer(name)
-    );
-    await Promise.allSettled(startPromises);
+  async startServers(): Promise<ServerStartReport> {
+    return this.startServersByName(Array.from(this.upstreamServers.keys()));
   }
 
   /**
    * Start specific servers by name
+   * Best-effort: a failing server does not prevent the others from starting
    */
-  async startServersByName(names: string[]): Promise<void> {
-    const startPromises = names.map(name => this.startServer(name));
-    await Promise.allSettled(startPromises);
+  async startServersByName(names: string[]): Promise<ServerStartReport> {
+    const results = await Promise.allSettled(names.map(name => this.startServer(name)));
+
+    const report: ServerStartReport = { started: [], failed: [] };
+    results.forEach((result, index) => {
+      if (result.status === 'fulfilled') {
+        report.started.push(names[index]);
+      } else {
+        const reason = result.reason;
+        report.failed.push({
+          name: names[index],
+          error: reason instanceof Error ? reason.message : String(reason),
+        });
+      }
+    });
+
+    return report;
   }
 
   /**
    * Start a single server
+   * Throws if the server is unknown or fails to initialize
    */
   private async startServer(name: string): Promise<void> {
     const server = this.upstreamServers.get(name);
     if (!server) {
       this.logger.warn(`Server not found: ${name}`);
-      return;
+      throw new Error(`Server not found: ${name}`);
     }
 
     if (server.connected) {
       return;
     }
 
+    let proc: ChildProcess | undefined;
     try {
-      const { command, args = [], env = {} } = server.config;
+      const { command, args = [], env = {} } = resolveServerEnv(
+        server.config,
+        (variable) => process.env[variable]
+      );
 
-      const proc = spawn(command, args, {
+      const spawned = spawn(command, args, {
         stdio: ['pipe', 'pipe', 'pipe'],
         env: { ...process.env, ...env },
         cwd: this.cwd,
       });
 
-      server.process = proc;
+      proc = spawned;
+      server.process = spawned;
+      server.state = 'starting';
 
       // Handle stdout
-      proc.stdout?.on('data', (data: Buffer) => {
+      spawned.stdout?.on('data', (data: Buffer) => {
         server.buffer += data.toString();
         this.processBuffer(server);
       });
 
       // Handle stderr
-      proc.stderr?.on('data', (data: Buffer) => {
+      spawned.stderr?.on('data', (data: Buffer) => {
         this.logger.debug(`[${name}] stderr: ${data.toString()}`);
       });
 
-      // Handle process events
-      proc.on('error', (err) => {
+      // Handle process events; ignore a process that has since been replaced
+      spawned.on('error', (err) => {
         this.logger.error(`[${name}] process error: ${err.message}`);
-        server.connected = false;
+        if (server.process === spawned) this.markCrashed(server);
       });
 
-      proc.on('close', (code) => {
+      spawned.on('close', (code) => {
         this.logger.debug(`[${name}] process closed with code ${code}`);
-        server.connected = false;
+        if (server.process === spawned) this.markCrashed(server);
       });
 
       // Send initialize request
       await this.initializeServer(server);
 
       server.connected = true;
+      server.state = 'running';
       this.logger.info(`Server started: ${name}`);
 
     } catch (error) {
       this.logger.error(`Failed to start server ${name}: ${error instanceof Error ? error.message : String(error)}`);
+      // Don't leave a half-started process behind
+      if (proc && server.process === proc) {
+        proc.kill();
+        server.process = undefined;
+        server.buffer = '';
+      }
+      this.markCrashed(server);
+      throw error;
     }
   }
 
+  /**
+   * Record that a server's process is gone
+   */
+  private markCrashed(server: UpstreamServerInfo): void {
+    server.connected = false;
+    server.state = 'crashed';
+  }
+
   /**
    * Initialize a server with MCP handshake
    */
@@ -310,6 +385,44 @@ export class StdioRouter extends EventEmitter {
     return this.sendToServer(server, request);
   }
 
+  /**
+   * Call a tool on a specific server
+   * @param toolName - Tool name as known to the server (without the server prefix)
+   * @throws ServerNotStartedError, ToolCallFailedError, MalformedResponseError
+   */
+  async callTool(serverName: string, toolName: string, args: Record<string, unknown> = {}): Promise<CallToolResult> {
+    const server = this.upstreamServers.get(serverName);
+    if (!server || !server.connected) {
+      throw new ServerNotStartedError(serverName);
+    }
+
+    let response: any;
+    try {
+      response = await this.sendToServer(server, {
+        jsonrpc: '2.0',
+        id: `call-${serverName}-${Date.now()}-${Math.random().toString(36).slice(2, 8)}`,
+        method: 'tools/call',
+        params: { name: toolName, arguments: args },
+      });
+    } catch (error) {
+      if (error instanceof ToolCallFailedError) throw error;
+      if (error instanceof Error) {
+        // Timeout or transport failure
+        throw new ToolCallFailedError(serverName, toolName, undefined, error.message, error);
+      }
+      // JSON-RPC error object from the server
+      const rpcError = error as { code?: number; message?: string };
+      throw new ToolCallFailedError(serverName, toolName, rpcError?.code, rpcError?.message ?? 'Unknown error', error);
+    }
+
+    const result = response?.result;
+    if (!result || typeof result !== 'object' || !Array.isArray(result.content)) {
+      throw new MalformedResponseError(serverName, 'expected result.content to be an array');
+    }
+
+    return result as CallToolResult;
+  }
+
   /**
    * Aggregate tools/list from all connected servers
    */
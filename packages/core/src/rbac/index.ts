@@ -10,14 +10,29 @@
 export {
   RoleManager,
   createRoleManager,
-  type RoleMemoryPermission
+  validateRole,
+  type MergeStrategy,
+  type PatternResolution,
+  type RoleManagerOptions,
+  type RoleMemoryPermission,
+  type RoleMergeReport,
+  type RoleRegistrationReport,
+  type RoleSnapshot,
+  type SkillConflictPolicy,
+  type ToolAccessDecision,
+  type ToolAccessTrace
 } from './role-manager.js';
 
 // Tool Visibility Manager
 export {
   ToolVisibilityManager,
   createToolVisibilityManager,
-  type ToolVisibilityOptions
+  type ToolVisibilityOptions,
+  type ToolVisibility,
+  type ToolVisibilityEntry,
+  type ToolRegistrationReport,
+  type ToolReplacementReport,
+  type RoleToolDiff
 } from './tool-visibility-manager.js';
 
 // Role Memory Store
@@ -27,5 +42,6 @@ export {
   type MemoryEntry,
   type RoleMemory,
   type MemorySearchOptions,
-  type SaveMemoryOptions
+  type SaveMemoryOptions,
+  type MemoryAuthorizer
 } from './role-memory.js';
@@ -9,11 +9,14 @@ import type {
   ListRolesOptions,
   ListRolesResult,
   SkillManifest,
+  BaseSkillDefinition,
   DynamicRole,
   RoleManifest,
   MemoryPolicy,
-  ToolPermissions
+  ToolPermissions,
+  VisibilityReason
 } from '@mycelium/shared';
+import { RoleValidationError, SkillConflictError, compileGlobPattern, getRoleTimeWindow, globMatch } from '@mycelium/shared';
 
 /**
  * Memory permission configuration for a role
@@ -25,6 +28,93 @@ export interface RoleMemoryPermission {
   teamRoles?: string[];
 }
 
+/**
+ * Access decision for a tool, with the reason it was reached
+ */
+export interface ToolAccessDecision {
+  /** Whether the tool is allowed */
+  allowed: boolean;
+  /** Which rule produced the decision */
+  reason: VisibilityReason;
+}
+
+/**
+ * Access decision with the inheritance chain and the role whose rule produced it
+ */
+export interface ToolAccessTrace extends ToolAccessDecision {
+  /** Inheritance chain from the role to its root */
+  chain: string[];
+  /** Role in the chain that contributed the deciding rule, if any */
+  sourceRoleId?: string;
+  /** The deciding entry: a tool name, a pattern, or a server name */
+  rule?: string;
+}
+
+/**
+ * How conflicting allow/deny rules are resolved
+ * - 'deny_wins': Any matching deny rule wins (default)
+ * - 'most_specific': The matching rule with the most literal characters wins;
+ *   exact names beat patterns, and deny wins ties
+ */
+export type PatternResolution = 'deny_wins' | 'most_specific';
+
+/**
+ * Result of registering several roles at once
+ */
+export interface RoleRegistrationReport {
+  /** Role IDs that did not exist before */
+  added: string[];
+  /** Role IDs that replaced an existing role (last one wins) */
+  overwritten: string[];
+}
+
+/**
+ * A role definition together with its memory permission, as stored by RoleManager
+ */
+export interface RoleSnapshot {
+  role: Role;
+  /** Omitted when the role has no memory grant */
+  memoryPermission?: RoleMemoryPermission;
+}
+
+/**
+ * How RoleManager.merge resolves roles and skills present in both managers
+ * - 'overlay': the other manager's definition wins
+ * - 'keep_existing': this manager's definition is kept
+ */
+export type MergeStrategy = 'overlay' | 'keep_existing';
+
+/**
+ * Result of merging another RoleManager
+ */
+export interface RoleMergeReport {
+  /** Role IDs that only existed in the other manager */
+  added: string[];
+  /** Role IDs defined in both managers (resolved by the strategy) */
+  conflicts: string[];
+}
+
+/**
+ * What to do when a role ends up with two conflicting skills
+ * - 'warn': Log a warning and load the role anyway (default)
+ * - 'reject': Throw SkillConflictError and leave the current roles unchanged
+ */
+export type SkillConflictPolicy = 'warn' | 'reject';
+
+/**
+ * Options for RoleManager
+ */
+export interface RoleManagerOptions {
+  /**
+   * When a role has deny rules but no allow rules, allow the remaining tools of its servers.
+   * Off by default: any tool permissions turn the role into an allow-list role.
+   */
+  defaultAllowOnServerAccess?: boolean;
+
+  /** Handling of conflicting skills on one role (default: 'warn') */
+  skillConflictPolicy?: SkillConflictPolicy;
+}
+
 /**
  * Role Manager
  * Manages role definitions and permission checking (skill-driven architecture)
@@ -38,8 +128,26 @@ export class RoleManager {
   /** Memory permissions per role (derived from skills) */
   private memoryPermissions: Map<string, RoleMemoryPermission> = new Map();
 
-  constructor(logger: Logger) {
+  /** Skill definitions from the last loaded manifest */
+  private skills: Map<string, BaseSkillDefinition> = new Map();
+
+  /** Memoized inheritance results, invalidated whenever the role set changes */
+  private effectiveServersCache: Map<string, string[]> = new Map();
+  private effectivePermissionsCache: Map<string, ToolPermissions> = new Map();
+
+  /** Frozen copies handed out by the accessors, rebuilt on the next read after a role change */
+  private frozenRoles: Map<string, Role> = new Map();
+
+  /** Whether server access implies tool access when no allow rules exist */
+  private defaultAllowOnServerAccess: boolean;
+
+  /** How conflicting skills on one role are handled */
+  private skillConflictPolicy: SkillConflictPolicy;
+
+  constructor(logger: Logger, options?: RoleManagerOptions) {
     this.logger = logger;
+    this.defaultAllowOnServerAccess = options?.defaultAllowOnServerAccess ?? false;
+    this.skillConflictPolicy = options?.skillConflictPolicy ?? 'warn';
     this.logger.debug('RoleManager initialized');
   }
 
@@ -58,12 +166,17 @@ export class RoleManager {
   // Role Accessors
   // ============================================================================
 
+  /**
+   * Get a role
+   * The result is frozen and shared between callers, so treat it as read-only; use updateRole to change it
+   */
   getRole(roleId: string): Role | undefined {
-    return this.roles.get(roleId);
+    const role = this.roles.get(roleId);
+    return role && this.getFrozenRole(roleId, role);
   }
 
   getDefaultRole(): Role | undefined {
-    return this.roles.get(this.defaultRole);
+    return this.getRole(this.defaultRole);
   }
 
   getDefaultRoleId(): string {
@@ -78,8 +191,167 @@ export class RoleManager {
     return Array.from(this.roles.keys());
   }
 
+  /**
+   * Get every role; like getRole, the results are frozen and read-only
+   */
   getAllRoles(): Role[] {
-    return Array.from(this.roles.values());
+    return Array.from(this.roles, ([roleId, role]) => this.getFrozenRole(roleId, role));
+  }
+
+  private getFrozenRole(roleId: string, role: Role): Role {
+    let frozen = this.frozenRoles.get(roleId);
+    if (!frozen) {
+      frozen = freezeRoleCopy(role);
+      this.frozenRoles.set(roleId, frozen);
+    }
+    return frozen;
+  }
+
+  /**
+   * Register a role, replacing any existing role with the same ID
+   * Only tool patterns are checked; see registerRoleValidated for the full checks
+   * The role is copied, so later changes to the argument have no effect
+   * @throws InvalidPatternError if an allow/deny pattern cannot be matched as written
+   */
+  registerRole(role: Role): void {
+    validateToolPatterns(role.toolPermissions);
+    this.roles.set(role.id, structuredClone(role));
+    this.clearCache();
+    this.logger.debug(`Registered role: ${role.id}`);
+  }
+
+  /**
+   * Register several roles, reporting which ones replaced an existing role
+   * @throws InvalidPatternError if any role has a bad pattern; no role is registered
+   */
+  registerRolesChecked(roles: Role[]): RoleRegistrationReport {
+    const report: RoleRegistrationReport = { added: [], overwritten: [] };
+
+    // Reject the whole batch before registering any of it
+    for (const role of roles) {
+      validateToolPatterns(role.toolPermissions);
+    }
+
+    for (const role of roles) {
+      if (this.roles.has(role.id)) {
+        report.overwritten.push(role.id);
+        this.logger.warn(`Role '${role.id}' overwritten by a later definition`);
+      } else {
+        report.added.push(role.id);
+      }
+      this.registerRole(role);
+    }
+
+    return report;
+  }
+
+  /**
+   * Clear memoized effective servers, tool permissions, and frozen role copies
+   */
+  clearCache(): void {
+    this.effectiveServersCache.clear();
+    this.effectivePermissionsCache.clear();
+    this.frozenRoles.clear();
+  }
+
+  /**
+   * Register a role after checking it is well-formed
+   * @throws RoleValidationError if the role is malformed
+   * @throws InvalidTimeWindowError if metadata.allowedTime is malformed
+   */
+  registerRoleValidated(role: Role): void {
+    validateRole(role);
+    this.registerRole(role);
+  }
+
+  /**
+   * Edit a registered role in place
+   * Memoized inheritance results are invalidated afterwards
+   * @returns false if the role does not exist
+   */
+  updateRole(roleId: string, edit: (role: Role) => void): boolean {
+    const role = this.roles.get(roleId);
+    if (!role) return false;
+
+    edit(role);
+    this.clearCache();
+    this.logger.debug(`Updated role: ${roleId}`);
+    return true;
+  }
+
+  /**
+   * Merge roles, skills, and memory permissions from another manager
+   * Memory permissions follow the role: whichever definition wins keeps its grant
+   */
+  merge(other: RoleManager, strategy: MergeStrategy = 'overlay'): RoleMergeReport {
+    const report: RoleMergeReport = { added: [], conflicts: [] };
+
+    for (const [roleId, role] of other.roles) {
+      const exists = this.roles.has(roleId);
+      if (exists) {
+        report.conflicts.push(roleId);
+        if (strategy === 'keep_existing') continue;
+      } else {
+        report.added.push(roleId);
+      }
+
+      // Copy so that updateRole on either manager does not affect the other
+      this.roles.set(roleId, structuredClone(role));
+      const memoryPermission = other.memoryPermissions.get(roleId);
+      if (memoryPermission) {
+        this.memoryPermissions.set(roleId, structuredClone(memoryPermission));
+      } else {
+        this.memoryPermissions.delete(roleId);
+      }
+    }
+
+    for (const [skillId, skill] of other.skills) {
+      if (strategy === 'overlay' || !this.skills.has(skillId)) {
+        this.skills.set(skillId, skill);
+      }
+    }
+
+    if (!this.roles.has(this.defaultRole) && other.roles.has(other.defaultRole)) {
+      this.defaultRole = other.defaultRole;
+    }
+
+    this.clearCache();
+    this.logger.info(
+      `Merged ${other.roles.size} roles (${report.added.length} added, ${report.conflicts.length} conflicts, strategy: ${strategy})`
+    );
+    return report;
+  }
+
+  /**
+   * Copy every role definition and its memory permission
+   */
+  snapshotRoles(): RoleSnapshot[] {
+    return Array.from(this.roles.values(), role => {
+      const memoryPermission = this.memoryPermissions.get(role.id);
+      return memoryPermission
+        ? { role: structuredClone(role), memoryPermission: structuredClone(memoryPermission) }
+        : { role: structuredClone(role) };
+    });
+  }
+
+  /**
+   * Replace all roles and memory permissions with a snapshot; skills are kept
+   * @param defaultRole - Default role ID; kept unchanged if omitted or not in the snapshot
+   */
+  restoreRoles(snapshot: RoleSnapshot[], defaultRole?: string): void {
+    this.roles.clear();
+    this.memoryPermissions.clear();
+    for (const { role, memoryPermission } of snapshot) {
+      this.roles.set(role.id, structuredClone(role));
+      if (memoryPermission) {
+        this.memoryPermissions.set(role.id, structuredClone(memoryPermission));
+      }
+    }
+    if (defaultRole && this.roles.has(defaultRole)) {
+      this.defaultRole = defaultRole;
+    }
+    this.clearCache();
+    this.logger.debug(`Restored ${snapshot.length} roles`);
   }
 
   // ============================================================================
@@ -115,6 +387,9 @@ export class RoleManager {
    * Get effective servers for a role (including inherited)
    */
   getEffectiveServers(roleId: string): string[] {
+    const cached = this.effectiveServersCache.get(roleId);
+    if (cached) return [...cached];
+
     const chain = this.getInheritanceChain(roleId);
     const servers = new Set<string>();
 
@@ -127,7 +402,9 @@ export class RoleManager {
       }
     }
 
-    return Array.from(servers);
+    const result = Array.from(servers);
+    this.effectiveServersCache.set(roleId, result);
+    return [...result];
   }
 
   /**
@@ -135,6 +412,9 @@ export class RoleManager {
    * Child permissions take precedence over parent
    */
   getEffectiveToolPermissions(roleId: string): ToolPermissions {
+    const cached = this.effectivePermissionsCache.get(roleId);
+    if (cached) return copyToolPermissions(cached);
+
     const chain = this.getInheritanceChain(roleId);
 
     // Start with empty permissions and merge from root to child
@@ -159,7 +439,8 @@ export class RoleManager {
       }
     }
 
-    return effective;
+    this.effectivePermissionsCache.set(roleId, effective);
+    return copyToolPermissions(effective);
   }
 
   // ============================================================================
@@ -182,12 +463,24 @@ export class RoleManager {
    * Check if a tool is allowed for a role (including inherited permissions)
    */
   isToolAllowedForRole(roleId: string, toolName: string, serverName: string): boolean {
+    return this.getToolAccessDecision(roleId, toolName, serverName).allowed;
+  }
+
+  /**
+   * Decide whether a tool is allowed for a role and report which rule decided it
+   */
+  getToolAccessDecision(
+    roleId: string,
+    toolName: string,
+    serverName: string,
+    resolution: PatternResolution = 'deny_wins'
+  ): ToolAccessDecision {
     const role = this.roles.get(roleId);
-    if (!role) return false;
+    if (!role) return { allowed: false, reason: 'no_role' };
 
     // Check server access first (uses inheritance)
     if (!this.isServerAllowedForRole(roleId, serverName)) {
-      return false;
+      return { allowed: false, reason: 'server_not_allowed' };
     }
 
     // Get effective permissions (merged with inherited roles)
@@ -200,57 +493,156 @@ export class RoleManager {
                           (permissions.denyPatterns?.length ?? 0) > 0;
 
     if (!hasPermissions) {
-      return true;
+      return { allowed: true, reason: 'server_access_granted' };
+    }
+
+    if (resolution === 'most_specific') {
+      return this.resolveMostSpecific(toolName, permissions);
     }
 
     // Check explicit deny list (deny takes precedence)
     if (permissions.deny?.includes(toolName)) {
-      return false;
+      return { allowed: false, reason: 'explicitly_denied' };
     }
 
     // Check deny patterns
     if (permissions.denyPatterns) {
       for (const pattern of permissions.denyPatterns) {
         if (this.matchPattern(toolName, pattern)) {
-          return false;
+          return { allowed: false, reason: 'denied_by_pattern' };
         }
       }
     }
 
     // Check explicit allow list
     if (permissions.allow?.includes(toolName)) {
-      return true;
+      return { allowed: true, reason: 'allowed_by_permission' };
     }
 
     // Check allow patterns
     if (permissions.allowPatterns) {
       for (const pattern of permissions.allowPatterns) {
         if (this.matchPattern(toolName, pattern)) {
-          return true;
+          return { allowed: true, reason: 'allowed_by_permission' };
         }
       }
     }
 
+    // Deny-only roles: server access covers the remaining tools if enabled
+    if (this.defaultAllowOnServerAccess && !this.hasAllowRules(permissions)) {
+      return { allowed: true, reason: 'server_access_granted' };
+    }
+
     // Default: deny if not explicitly allowed
-    return false;
+    return { allowed: false, reason: 'not_in_allow_list' };
   }
 
   /**
-   * Match a string against a pattern (supports * wildcard)
+   * Find the deny rule (explicit or pattern) that matches a tool, ignoring allow rules
+   * @returns the deny decision, or undefined if no deny rule matches
    */
-  private matchPattern(str: string, pattern: string): boolean {
-    // Direct match
-    if (str === pattern) return true;
+  getToolDenyDecision(roleId: string, toolName: string): ToolAccessDecision | undefined {
+    const permissions = this.getEffectiveToolPermissions(roleId);
+    if (permissions.deny?.includes(toolName)) {
+      return { allowed: false, reason: 'explicitly_denied' };
+    }
+    if (permissions.denyPatterns?.some(pattern => this.matchPattern(toolName, pattern))) {
+      return { allowed: false, reason: 'denied_by_pattern' };
+    }
+    return undefined;
+  }
+
+  /**
+   * Explain a deny_wins access decision by naming the role in the inheritance
+   * chain that contributed the deciding rule (the nearest one, child first)
+   */
+  traceToolAccess(roleId: string, toolName: string, serverName: string): ToolAccessTrace {
+    const decision = this.getToolAccessDecision(roleId, toolName, serverName);
+    const chain = this.getInheritanceChain(roleId);
+    const trace: ToolAccessTrace = { ...decision, chain };
+
+    const find = (match: (role: Role) => string | undefined): void => {
+      for (const id of chain) {
+        const role = this.roles.get(id);
+        const rule = role ? match(role) : undefined;
+        if (rule !== undefined) {
+          trace.sourceRoleId = id;
+          trace.rule = rule;
+          return;
+        }
+      }
+    };
 
-    // Wildcard matching
-    if (pattern.includes('*')) {
-      const regexPattern = pattern
-        .replace(/[.+?^${}()|[\]\\]/g, '\\$&')
-        .replace(/\*/g, '.*');
-      return new RegExp(`^${regexPattern}$`).test(str);
+    switch (decision.reason) {
+      case 'explicitly_denied':
+        find(role => role.toolPermissions?.deny?.includes(toolName) ? toolName : undefined);
+        break;
+      case 'denied_by_pattern':
+        find(role => role.toolPermissions?.denyPatterns?.find(p => this.matchPattern(toolName, p)));
+        break;
+      case 'allowed_by_permission':
+        find(role => role.toolPermissions?.allow?.includes(toolName)
+          ? toolName
+          : role.toolPermissions?.allowPatterns?.find(p => this.matchPattern(toolName, p)));
+        break;
+      case 'server_access_granted':
+        find(role => role.allowedServers.find(server => server === serverName || server === '*'));
+        break;
     }
 
-    return false;
+    return trace;
+  }
+
+  private hasAllowRules(permissions: ToolPermissions): boolean {
+    return (permissions.allow?.length ?? 0) > 0 || (permissions.allowPatterns?.length ?? 0) > 0;
+  }
+
+  /**
+   * Apply the verdict of the most specific matching rule
+   */
+  private resolveMostSpecific(toolName: string, permissions: ToolPermissions): ToolAccessDecision {
+    // Exact names outrank any pattern that matches the same tool
+    const exactScore = toolName.length + 1;
+    const literalScore = (pattern: string) => pattern.replace(/\*/g, '').length;
+
+    const candidates: Array<ToolAccessDecision & { score: number }> = [];
+
+    if (permissions.deny?.includes(toolName)) {
+      candidates.push({ allowed: false, reason: 'explicitly_denied', score: exactScore });
+    }
+    if (permissions.allow?.includes(toolName)) {
+      candidates.push({ allowed: true, reason: 'allowed_by_permission', score: exactScore });
+    }
+    for (const pattern of permissions.denyPatterns ?? []) {
+      if (this.matchPattern(toolName, pattern)) {
+        candidates.push({ allowed: false, reason: 'denied_by_pattern', score: literalScore(pattern) });
+      }
+    }
+    for (const pattern of permissions.allowPatterns ?? []) {
+      if (this.matchPattern(toolName, pattern)) {
+        candidates.push({ allowed: true, reason: 'allowed_by_permission', score: literalScore(pattern) });
+      }
+    }
+
+    if (candidates.length === 0) {
+      return this.defaultAllowOnServerAccess && !this.hasAllowRules(permissions)
+        ? { allowed: true, reason: 'server_access_granted' }
+        : { allowed: false, reason: 'not_in_allow_list' };
+    }
+
+    // Highest score wins; deny candidates come first so they win ties
+    const best = candidates.reduce((winner, candidate) =>
+      candidate.score > winner.score ? candidate : winner
+    );
+    return { allowed: best.allowed, reason: best.reason };
+  }
+
+  /**
+   * Match a string against a pattern (supports * wildcard)
+   * Compiled patterns are cached by globMatch, so repeated checks stay cheap
+   */
+  private matchPattern(str: string, pattern: string): boolean {
+    return globMatch(pattern, str);
   }
 
   // ============================================================================
@@ -259,14 +651,14 @@ export class RoleManager {
 
   /**
    * Get memory permission for a role (direct, without inheritance)
-   * Returns 'none' if no memory skill is granted
+   * Returns 'none' if no memory skill is granted; the result is a copy, so use setMemoryPermission to change it
    */
   getMemoryPermission(roleId: string): RoleMemoryPermission {
     const permission = this.memoryPermissions.get(roleId);
     if (!permission) {
       return { policy: 'none' };
     }
-    return permission;
+    return structuredClone(permission);
   }
 
   /**
@@ -315,6 +707,50 @@ export class RoleManager {
     return highestPermission;
   }
 
+  /** Default depth for transitive team resolution */
+  static readonly DEFAULT_TEAM_DEPTH = 3;
+
+  /**
+   * Get effective memory permission with transitive team resolution
+   * For 'team' policy, team members that are themselves team leads contribute
+   * their team roles too, walking the team graph up to maxDepth levels.
+   * Cycles are skipped; the resolved policy itself is never escalated.
+   */
+  getEffectiveMemoryPermissionTransitive(
+    roleId: string,
+    maxDepth: number = RoleManager.DEFAULT_TEAM_DEPTH
+  ): RoleMemoryPermission {
+    const permission = this.getEffectiveMemoryPermission(roleId);
+    if (permission.policy !== 'team') {
+      return permission;
+    }
+
+    const visited = new Set<string>([roleId]);
+    const teamRoles: string[] = [];
+    let frontier = permission.teamRoles || [];
+
+    for (let depth = 0; depth < maxDepth && frontier.length > 0; depth++) {
+      const next: string[] = [];
+
+      for (const teamRole of frontier) {
+        if (visited.has(teamRole)) {
+          continue;
+        }
+        visited.add(teamRole);
+        teamRoles.push(teamRole);
+
+        const teamPermission = this.getEffectiveMemoryPermission(teamRole);
+        if (teamPermission.policy === 'team' && teamPermission.teamRoles) {
+          next.push(...teamPermission.teamRoles);
+        }
+      }
+
+      frontier = next;
+    }
+
+    return { policy: 'team', teamRoles };
+  }
+
   /**
    * Check if a role has memory access
    */
@@ -354,6 +790,7 @@ export class RoleManager {
 
   /**
    * Set memory permission for a role (used during skill loading)
+   * The permission is copied, so later changes to the argument have no effect
    */
   setMemoryPermission(roleId: string, permission: RoleMemoryPermission): void {
     // Higher privilege wins: all > team > isolated > none
@@ -377,7 +814,7 @@ export class RoleManager {
       }
     }
 
-    this.memoryPermissions.set(roleId, permission);
+    this.memoryPermissions.set(roleId, structuredClone(permission));
     this.logger.debug(`Set memory permission for role ${roleId}: ${permission.policy}`);
   }
 
@@ -470,12 +907,30 @@ export class RoleManager {
 
   /**
    * Load roles from skill manifest
+   * @throws InvalidPatternError if a skill's allowedTools entry cannot be matched as written
    */
   async loadFromSkillManifest(manifest: SkillManifest): Promise<void> {
     const roleManifest = this.generateRoleManifest(manifest);
 
+    const skillsById = new Map(manifest.skills.map(skill => [skill.id, skill]));
+    for (const [roleId, dynamicRole] of Object.entries(roleManifest.roles)) {
+      validateToolPatterns({ allowPatterns: dynamicRole.tools });
+      for (const [skillId, conflictingSkillId] of findSkillConflicts(dynamicRole.skills, skillsById)) {
+        if (this.skillConflictPolicy === 'reject') {
+          throw new SkillConflictError(roleId, skillId, conflictingSkillId);
+        }
+        this.logger.warn(`Role '${roleId}' has conflicting skills: ${skillId}, ${conflictingSkillId}`);
+      }
+    }
+
     this.roles.clear();
     this.memoryPermissions.clear();
+    this.clearCache();
+
+    this.skills.clear();
+    for (const skill of manifest.skills) {
+      this.skills.set(skill.id, skill);
+    }
 
     // Extract memory grants from skills
     for (const skill of manifest.skills) {
@@ -527,6 +982,70 @@ export class RoleManager {
     this.logger.info(`Loaded ${this.roles.size} roles from skill manifest`);
   }
 
+  /**
+   * Get loaded skills carrying a metadata tag
+   */
+  skillsByTag(tag: string): BaseSkillDefinition[] {
+    return Array.from(this.skills.values()).filter(skill => skill.metadata?.tags?.includes(tag));
+  }
+
+  /**
+   * Get role IDs that have at least one skill carrying a metadata tag
+   */
+  rolesWithSkillTag(tag: string): string[] {
+    const roleIds = new Set<string>();
+    for (const skill of this.skillsByTag(tag)) {
+      for (const roleId of skill.allowedRoles) {
+        if (roleId !== '*' && this.roles.has(roleId)) {
+          roleIds.add(roleId);
+        }
+      }
+    }
+    return Array.from(roleIds);
+  }
+
+  /**
+   * List pairs of mutually exclusive skills granted to a role
+   */
+  conflictsForRole(roleId: string): Array<[string, string]> {
+    const skillIds = this.roles.get(roleId)?.metadata?.skills ?? [];
+    return findSkillConflicts(skillIds, this.skills);
+  }
+
+  /**
+   * Export the loaded roles as a skill manifest
+   *
+   * Lossy: original skill groupings are not recoverable, so each role becomes
+   * one synthetic skill ('role-<id>') granting its effective allow list and
+   * allow patterns. Deny rules and server-only access cannot be expressed as
+   * skills and are dropped.
+   */
+  exportRolesAsManifest(): SkillManifest {
+    const skills = this.getAllRoles().map(role => {
+      const permissions = this.getEffectiveToolPermissions(role.id);
+      const tools = [...new Set([...(permissions.allow ?? []), ...(permissions.allowPatterns ?? [])])];
+      const memory = this.getMemoryPermission(role.id);
+
+      const skill: BaseSkillDefinition = {
+        id: `role-${role.id}`,
+        displayName: role.name,
+        description: role.description,
+        allowedRoles: [role.id],
+        allowedTools: tools
+      };
+      if (memory.policy !== 'none') {
+        skill.grants = { memory: memory.policy, memoryTeamRoles: memory.teamRoles };
+      }
+      return skill;
+    });
+
+    return {
+      skills,
+      version: '1.0.0',
+      generatedAt: new Date()
+    };
+  }
+
   // ============================================================================
   // Tool/Server Extraction
   // ============================================================================
@@ -611,9 +1130,89 @@ Available tools are filtered based on your role.`;
 // Factory
 // ============================================================================
 
+/**
+ * Copy tool permissions so cached arrays are never shared with callers
+ */
+function copyToolPermissions(permissions: ToolPermissions): ToolPermissions {
+  return {
+    allow: [...(permissions.allow ?? [])],
+    deny: [...(permissions.deny ?? [])],
+    allowPatterns: [...(permissions.allowPatterns ?? [])],
+    denyPatterns: [...(permissions.denyPatterns ?? [])]
+  };
+}
+
+/**
+ * Deep-copy a role and freeze the copy, so callers cannot edit a stored role behind the cache
+ */
+function freezeRoleCopy(role: Role): Role {
+  return deepFreeze(structuredClone(role));
+}
+
+function deepFreeze<T>(value: T): T {
+  if (value && typeof value === 'object' && !Object.isFrozen(value)) {
+    Object.freeze(value);
+    for (const child of Object.values(value)) {
+      deepFreeze(child);
+    }
+  }
+  return value;
+}
+
+/**
+ * Find pairs of skills that declare each other (in either direction) as conflicting
+ */
+function findSkillConflicts(
+  skillIds: string[],
+  skills: Map<string, BaseSkillDefinition>
+): Array<[string, string]> {
+  const pairs: Array<[string, string]> = [];
+  for (let i = 0; i < skillIds.length; i++) {
+    for (let j = i + 1; j < skillIds.length; j++) {
+      const a = skillIds[i];
+      const b = skillIds[j];
+      if (skills.get(a)?.conflicts?.includes(b) || skills.get(b)?.conflicts?.includes(a)) {
+        pairs.push([a, b]);
+      }
+    }
+  }
+  return pairs;
+}
+
+/**
+ * Check that a role is well-formed
+ * @throws RoleValidationError on an empty ID, control characters in the ID, or self-inheritance
+ * @throws InvalidPatternError if an allow/deny pattern cannot be matched as written
+ * @throws InvalidTimeWindowError if metadata.allowedTime is not a valid "HH:MM-HH:MM" window
+ */
+export function validateRole(role: Role): void {
+  if (role.id.trim().length === 0) {
+    throw new RoleValidationError(role.id, 'empty_id');
+  }
+  // eslint-disable-next-line no-control-regex
+  if (/[\u0000-\u001f\u007f]/.test(role.id)) {
+    throw new RoleValidationError(role.id, 'control_characters');
+  }
+  if (role.inherits === role.id) {
+    throw new RoleValidationError(role.id, 'self_inheritance');
+  }
+  validateToolPatterns(role.toolPermissions);
+  getRoleTimeWindow(role);
+}
+
+/**
+ * Check that every allow/deny pattern compiles
+ * @throws InvalidPatternError on the first pattern that cannot be matched as written
+ */
+function validateToolPatterns(permissions: ToolPermissions | undefined): void {
+  for (const pattern of [...(permissions?.allowPatterns ?? []), ...(permissions?.denyPatterns ?? [])]) {
+    compileGlobPattern(pattern);
+  }
+}
+
 /**
  * Create a RoleManager instance
  */
-export function createRoleManager(logger: Logger): RoleManager {
-  return new RoleManager(logger);
+export function createRoleManager(logger: Logger, options?: RoleManagerOptions): RoleManager {
+  return new RoleManager(logger, options);
 }
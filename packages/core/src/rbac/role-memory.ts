@@ -6,6 +6,8 @@
 import { promises as fs } from 'fs';
 import path from 'path';
 import type { Logger } from '@mycelium/shared';
+import { MemoryAccessDeniedError } from '@mycelium/shared';
+import type { RoleManager } from './role-manager.js';
 
 // ============================================================================
 // Types
@@ -83,6 +85,11 @@ export interface MemorySearchOptions {
   query?: string;
 }
 
+/**
+ * Access policy used to authorize role-scoped reads and writes
+ */
+export type MemoryAuthorizer = Pick<RoleManager, 'canAccessRoleMemory' | 'canAccessAllMemories'>;
+
 /**
  * Options for saving memory
  */
@@ -101,6 +108,8 @@ export interface SaveMemoryOptions {
 // RoleMemoryStore Implementation
 // ============================================================================
 
+const MEMORY_FILE_SUFFIX = '.memory.md';
+
 /**
  * Role Memory Store - Manages persistent memory for each role
  *
@@ -118,9 +127,12 @@ export class RoleMemoryStore {
   private locks: Map<string, Promise<void>> = new Map();
   // Roles that can access all memories (e.g., admin)
   private static readonly SUPER_ROLES = ['admin'];
+  // Policy source for the *As methods (typically the RoleManager)
+  private authorizer: MemoryAuthorizer;
 
-  constructor(memoryDir: string = './memory', logger?: Logger) {
+  constructor(memoryDir: string, authorizer: MemoryAuthorizer, logger?: Logger) {
     this.memoryDir = memoryDir;
+    this.authorizer = authorizer;
     this.logger = logger || { debug: () => {}, info: () => {}, warn: () => {}, error: () => {} };
   }
 
@@ -162,6 +174,7 @@ export class RoleMemoryStore {
   async initialize(): Promise<void> {
     try {
       await fs.mkdir(this.memoryDir, { recursive: true });
+      await this.migrateLegacyFileNames();
       this.logger.info(`Memory store initialized at ${this.memoryDir}`);
     } catch (error) {
       this.logger.error('Failed to initialize memory store', { error });
@@ -169,12 +182,60 @@ export class RoleMemoryStore {
     }
   }
 
+  /**
+   * Rename memory files written under the old '_'-substitution scheme
+   * The owning role is read from the file header; a file is left in place (and
+   * refused by load) when its encoded name is already taken by another file.
+   */
+  private async migrateLegacyFileNames(): Promise<void> {
+    const files = (await fs.readdir(this.memoryDir)).filter((f) => f.endsWith(MEMORY_FILE_SUFFIX));
+
+    for (const file of files) {
+      const filePath = path.join(this.memoryDir, file);
+      const owner = RoleMemoryStore.readOwner(await fs.readFile(filePath, 'utf-8'));
+      if (owner === null) continue;
+
+      const target = this.getMemoryPath(owner);
+      if (target === filePath) continue;
+
+      try {
+        await fs.access(target);
+        this.logger.warn(`Not migrating ${file}: ${path.basename(target)} already exists for role ${owner}`);
+        continue;
+      } catch {
+        // Target is free
+      }
+
+      await fs.rename(filePath, target);
+      this.logger.info(`Migrated legacy memory file ${file} → ${path.basename(target)}`);
+    }
+  }
+
+  /**
+   * Read the owning role ID from a memory file's '# Memory: <roleId>' header
+   */
+  private static readOwner(content: string): string | null {
+    const match = /^# Memory: (.*)$/.exec(content.split('\n', 1)[0]);
+    return match ? match[1].trim() : null;
+  }
+
   /**
    * Get the memory file path for a role
    */
   private getMemoryPath(roleId: string): string {
-    const safeRoleId = roleId.replace(/[^a-zA-Z0-9_-]/g, '_');
-    return path.join(this.memoryDir, `${safeRoleId}.memory.md`);
+    return path.join(this.memoryDir, `${RoleMemoryStore.encodeRoleId(roleId)}${MEMORY_FILE_SUFFIX}`);
+  }
+
+  /**
+   * Encode a role ID as a file name
+   * Percent-encodes every other character so distinct roles ('a.b', 'a_b') never share a file
+   */
+  private static encodeRoleId(roleId: string): string {
+    return roleId.replace(/[^a-zA-Z0-9_-]/g, (char) =>
+      Array.from(Buffer.from(char, 'utf-8'))
+        .map((byte) => `%${byte.toString(16).toUpperCase().padStart(2, '0')}`)
+        .join('')
+    );
   }
 
   /**
@@ -191,6 +252,11 @@ export class RoleMemoryStore {
 
     try {
       const content = await fs.readFile(memoryPath, 'utf-8');
+      const owner = RoleMemoryStore.readOwner(content);
+      if (owner !== null && owner !== roleId) {
+        // Unmigrated legacy file of another role: never serve or overwrite it
+        throw new Error(`Memory file ${path.basename(memoryPath)} belongs to role '${owner}', not '${roleId}'`);
+      }
       const memory = this.parseMarkdown(roleId, content);
       this.cache.set(roleId, memory);
       return memory;
@@ -221,9 +287,9 @@ export class RoleMemoryStore {
   }
 
   /**
-   * Add a memory entry for a role
+   * Add a memory entry for a role without an access check; callers go through writeAs
    */
-  async addEntry(
+  private async addEntry(
     roleId: string,
     content: string,
     options: SaveMemoryOptions = {}
@@ -258,9 +324,9 @@ export class RoleMemoryStore {
   }
 
   /**
-   * Search memory entries
+   * Search memory entries without an access check; callers go through readAs
    */
-  async search(
+  private async search(
     roleId: string,
     options: MemorySearchOptions = {}
   ): Promise<MemoryEntry[]> {
@@ -317,20 +383,21 @@ export class RoleMemoryStore {
   }
 
   /**
-   * Recall memory - get most relevant memories for a context
+   * Recall memory - get a role's own most relevant memories for a context
+   * Throws MemoryAccessDeniedError if the role has no memory grant
    */
   async recall(roleId: string, context: string, limit: number = 5): Promise<MemoryEntry[]> {
-    return this.search(roleId, {
+    return this.readAs(roleId, roleId, {
       query: context,
       limit,
     });
   }
 
   /**
-   * Search across ALL roles (admin only)
+   * Search across ALL roles without an access check; callers go through searchAllAs
    * Returns entries with their source role
    */
-  async searchAll(
+  private async searchAll(
     options: MemorySearchOptions = {}
   ): Promise<Array<MemoryEntry & { sourceRole: string }>> {
     const allRoles = await this.listRolesWithMemory();
@@ -400,6 +467,76 @@ export class RoleMemoryStore {
     return true;
   }
 
+  /**
+   * List entry IDs stored for a role
+   */
+  async keys(roleId: string): Promise<string[]> {
+    const memory = await this.load(roleId);
+    return memory.entries.map((e) => e.id);
+  }
+
+  /**
+   * Get all entries of a target role on behalf of an accessor role
+   * Throws if the accessor's memory policy does not cover the target
+   */
+  async entriesFor(accessorRoleId: string, targetRoleId: string): Promise<MemoryEntry[]> {
+    if (!this.authorizer.canAccessRoleMemory(accessorRoleId, targetRoleId)) {
+      throw new MemoryAccessDeniedError(accessorRoleId, targetRoleId, 'read');
+    }
+
+    const memory = await this.load(targetRoleId);
+    return [...memory.entries];
+  }
+
+  /**
+   * Search a target role's memory on behalf of an accessor role
+   * Throws MemoryAccessDeniedError if the accessor's policy does not cover the target
+   */
+  async readAs(
+    accessorRoleId: string,
+    targetRoleId: string,
+    options: MemorySearchOptions = {}
+  ): Promise<MemoryEntry[]> {
+    if (!this.authorizer.canAccessRoleMemory(accessorRoleId, targetRoleId)) {
+      throw new MemoryAccessDeniedError(accessorRoleId, targetRoleId, 'read');
+    }
+    return this.search(targetRoleId, options);
+  }
+
+  /**
+   * Search every role's memory on behalf of an accessor role
+   * Throws MemoryAccessDeniedError unless the accessor's policy is 'all'
+   */
+  async searchAllAs(
+    accessorRoleId: string,
+    options: MemorySearchOptions = {}
+  ): Promise<Array<MemoryEntry & { sourceRole: string }>> {
+    if (!this.authorizer.canAccessAllMemories(accessorRoleId)) {
+      throw new MemoryAccessDeniedError(accessorRoleId, '*', 'read');
+    }
+    return this.searchAll(options);
+  }
+
+  /**
+   * Add an entry to a target role's memory on behalf of an accessor role
+   * Roles may only write their own memory unless their policy is 'all'
+   */
+  async writeAs(
+    accessorRoleId: string,
+    targetRoleId: string,
+    content: string,
+    options: SaveMemoryOptions = {}
+  ): Promise<MemoryEntry> {
+    const allowed = accessorRoleId === targetRoleId
+      ? this.authorizer.canAccessRoleMemory(accessorRoleId, targetRoleId)
+      : this.authorizer.canAccessAllMemories(accessorRoleId);
+
+    if (!allowed) {
+      throw new MemoryAccessDeniedError(accessorRoleId, targetRoleId, 'write');
+    }
+    return this.addEntry(targetRoleId, content, options);
+  }
+
   /**
    * Clear all memory for a role
    */
@@ -413,14 +550,22 @@ export class RoleMemoryStore {
    * List all roles with memory
    */
   async listRolesWithMemory(): Promise<string[]> {
+    let files: string[];
     try {
-      const files = await fs.readdir(this.memoryDir);
-      return files
-        .filter((f) => f.endsWith('.memory.md'))
-        .map((f) => f.replace('.memory.md', ''));
+      files = await fs.readdir(this.memoryDir);
     } catch {
       return [];
     }
+
+    const roles: string[] = [];
+    for (const file of files.filter((f) => f.endsWith(MEMORY_FILE_SUFFIX))) {
+      try {
+        roles.push(decodeURIComponent(file.slice(0, -MEMORY_FILE_SUFFIX.length)));
+      } catch {
+        this.logger.warn(`Skipping memory file with an undecodable name: ${file}`);
+      }
+    }
+    return roles;
   }
 
   /**
@@ -649,6 +794,10 @@ export class RoleMemoryStore {
 /**
  * Create a new RoleMemoryStore instance
  */
-export function createRoleMemoryStore(memoryDir?: string, logger?: Logger): RoleMemoryStore {
-  return new RoleMemoryStore(memoryDir, logger);
+export function createRoleMemoryStore(
+  memoryDir: string,
+  authorizer: MemoryAuthorizer,
+  logger?: Logger
+): RoleMemoryStore {
+  return new RoleMemoryStore(memoryDir, authorizer, logger);
 }
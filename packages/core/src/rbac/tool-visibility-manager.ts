@@ -3,9 +3,10 @@
 // Manages tool discovery and role-based visibility filtering
 // ============================================================================
 
-import type { Logger, Role, ToolInfo, MemoryPolicy, SkillDefinition } from '@mycelium/shared';
+import type { Logger, Role, ToolInfo, MemoryPolicy, SkillDefinition, VisibilityReason } from '@mycelium/shared';
+import { SystemTools, ToolNotAccessibleError } from '@mycelium/shared';
 import type { Tool } from '@modelcontextprotocol/sdk/types.js';
-import { RoleManager } from './role-manager.js';
+import { RoleManager, type PatternResolution, type ToolAccessDecision } from './role-manager.js';
 
 /**
  * Options for ToolVisibilityManager
@@ -13,6 +14,59 @@ import { RoleManager } from './role-manager.js';
 export interface ToolVisibilityOptions {
   /** Initial skill definitions */
   skillDefinitions?: SkillDefinition[];
+
+  /** How conflicting allow/deny patterns are resolved (default: 'deny_wins') */
+  patternResolution?: PatternResolution;
+
+  /** Always-visible system tools (default: DEFAULT_SYSTEM_TOOLS) */
+  systemTools?: SystemTools;
+}
+
+/**
+ * Visibility of a single tool for a given role
+ */
+export interface ToolVisibility {
+  visible: boolean;
+  reason: VisibilityReason;
+}
+
+/**
+ * Visibility of a named tool, as listed by snapshotVisibility
+ */
+export interface ToolVisibilityEntry extends ToolVisibility {
+  name: string;
+}
+
+/**
+ * Result of registering a batch of tools
+ */
+export interface ToolRegistrationReport {
+  /** Tool names that were not registered before */
+  added: string[];
+  /** Tool names that replaced an existing registration (last one wins) */
+  overwritten: string[];
+}
+
+/**
+ * Result of swapping a server's tool set
+ */
+export interface ToolReplacementReport {
+  /** Tool names the server did not provide before */
+  added: string[];
+  /** Tool names the server no longer provides */
+  removed: string[];
+}
+
+/**
+ * Tools that differ in visibility between two roles
+ */
+export interface RoleToolDiff {
+  /** Tools visible only to the first role */
+  onlyA: string[];
+  /** Tools visible only to the second role */
+  onlyB: string[];
+  /** Tools visible to both roles */
+  both: string[];
 }
 
 /**
@@ -41,9 +95,23 @@ export class ToolVisibilityManager {
   // Whether skill-based filtering is enabled
   private skillFilteringEnabled: boolean = false;
 
+  // Servers whose tools are temporarily hidden (backend failed to start)
+  private unavailableServers: Set<string> = new Set();
+
+  // Resolution mode for conflicting allow/deny patterns
+  private patternResolution: PatternResolution;
+
+  // Tool aliases (alias name -> canonical name)
+  private aliases: Map<string, string> = new Map();
+
+  // Tools visible regardless of role or skills
+  private systemTools: SystemTools;
+
   constructor(logger: Logger, roleManager: RoleManager, options?: ToolVisibilityOptions) {
     this.logger = logger;
     this.roleManager = roleManager;
+    this.patternResolution = options?.patternResolution ?? 'deny_wins';
+    this.systemTools = options?.systemTools ?? new SystemTools();
 
     // Load initial skill definitions if provided
     if (options?.skillDefinitions) {
@@ -148,7 +216,8 @@ export class ToolVisibilityManager {
         sourceServer,
         prefixedName: tool.name,
         visible: true,
-        visibilityReason: 'discovered'
+        visibilityReason: 'discovered',
+        registeredAt: new Date()
       };
       this.allTools.set(tool.name, toolInfo);
     }
@@ -156,12 +225,41 @@ export class ToolVisibilityManager {
     this.logger.debug(`Registered ${tools.length} tools from ${sourceServer}`);
   }
 
+  /**
+   * Register tools from a server, reporting names that replaced an earlier registration
+   * Collisions within the same batch are reported too
+   */
+  registerToolsChecked(tools: Tool[], sourceServer: string): ToolRegistrationReport {
+    const report: ToolRegistrationReport = { added: [], overwritten: [] };
+
+    for (const tool of tools) {
+      const existing = this.allTools.get(tool.name);
+      if (existing) {
+        report.overwritten.push(tool.name);
+        this.logger.warn(
+          `Tool '${tool.name}' from ${sourceServer} overwrites the one registered by ${existing.sourceServer}`
+        );
+      } else {
+        report.added.push(tool.name);
+      }
+      this.registerTools([tool], sourceServer);
+    }
+
+    return report;
+  }
+
   /**
    * Register all tools from a raw tools list response
    */
   registerToolsFromList(toolsList: Tool[]): void {
-    this.allTools.clear();
+    // Keep tools of unavailable servers so they can be reported as hidden
+    for (const [name, toolInfo] of this.allTools) {
+      if (!this.unavailableServers.has(toolInfo.sourceServer)) {
+        this.allTools.delete(name);
+      }
+    }
 
+    const registeredAt = new Date();
     for (const tool of toolsList) {
       const { serverName } = this.parseToolName(tool.name);
 
@@ -170,7 +268,8 @@ export class ToolVisibilityManager {
         sourceServer: serverName,
         prefixedName: tool.name,
         visible: true,
-        visibilityReason: 'discovered'
+        visibilityReason: 'discovered',
+        registeredAt
       };
 
       this.allTools.set(tool.name, toolInfo);
@@ -179,12 +278,100 @@ export class ToolVisibilityManager {
     this.logger.info(`Registered ${this.allTools.size} tools from upstream servers`);
   }
 
+  /**
+   * Swap a server's tool set for a freshly listed one (e.g. after the server restarts)
+   * Tools the server no longer provides are removed; visible tools are refreshed
+   */
+  replaceServerTools(sourceServer: string, tools: Tool[]): ToolReplacementReport {
+    const newNames = new Set(tools.map(tool => tool.name));
+    const report: ToolReplacementReport = { added: [], removed: [] };
+
+    for (const [name, toolInfo] of this.allTools) {
+      if (toolInfo.sourceServer === sourceServer && !newNames.has(name)) {
+        this.allTools.delete(name);
+        report.removed.push(name);
+      }
+    }
+    for (const name of newNames) {
+      if (this.allTools.get(name)?.sourceServer !== sourceServer) {
+        report.added.push(name);
+      }
+    }
+
+    this.registerTools(tools, sourceServer);
+    this.updateVisibleTools();
+    return report;
+  }
+
+  /**
+   * Remove tools last registered before the cutoff
+   * Tools without a registration time are kept
+   * @returns Names of the removed tools
+   */
+  pruneOlderThan(cutoff: Date): string[] {
+    const removed: string[] = [];
+    for (const [name, toolInfo] of this.allTools) {
+      if (toolInfo.registeredAt && toolInfo.registeredAt < cutoff) {
+        this.allTools.delete(name);
+        removed.push(name);
+      }
+    }
+
+    if (removed.length > 0) {
+      this.updateVisibleTools();
+      this.logger.debug(`Pruned ${removed.length} stale tools`);
+    }
+    return removed;
+  }
+
+  /**
+   * Mark a server's tools as available or temporarily unavailable
+   * Unavailable tools are hidden independently of RBAC
+   */
+  setServerAvailability(serverName: string, available: boolean): void {
+    if (available) {
+      this.unavailableServers.delete(serverName);
+    } else {
+      this.unavailableServers.add(serverName);
+    }
+  }
+
+  /**
+   * Check if a server's tools are currently available
+   */
+  isServerAvailable(serverName: string): boolean {
+    return !this.unavailableServers.has(serverName);
+  }
+
+  /**
+   * Treat `alias` as another name for `canonical`
+   * Role and skill checks for the aliased tool use the canonical name (and its server),
+   * so a grant of the canonical tool also covers the alias; deny rules match either name
+   */
+  addAlias(canonical: string, alias: string): void {
+    const target = this.resolveAlias(canonical);
+    if (target === alias) {
+      throw new Error(`Tool alias '${alias}' would refer to itself`);
+    }
+    this.aliases.set(alias, target);
+    this.updateVisibleTools();
+    this.logger.debug(`Registered tool alias: ${alias} → ${target}`);
+  }
+
+  /**
+   * Get the canonical name for a tool (the name itself if it is not an alias)
+   */
+  resolveAlias(toolName: string): string {
+    return this.aliases.get(toolName) ?? toolName;
+  }
+
   /**
    * Clear all registered tools
    */
   clearTools(): void {
     this.allTools.clear();
     this.visibleTools.clear();
+    this.unavailableServers.clear();
   }
 
   // ============================================================================
@@ -215,39 +402,23 @@ export class ToolVisibilityManager {
     this.visibleTools.clear();
 
     const roleId = this.currentRole?.id || 'none';
-    const allowedServers = this.currentRole?.allowedServers || [];
-    const skillAllowedTools = this.skillFilteringEnabled ? this.getAllowedToolsFromSkills() : null;
-
     this.logger.debug(`Filtering tools for role: ${roleId}, skills: [${this.activeSkills.join(', ')}]`);
 
     let roleFilteredCount = 0;
     let skillFilteredCount = 0;
 
-    for (const [name, toolInfo] of this.allTools) {
-      // Step 1: Role-based filtering
-      const isRoleAllowed = this.isToolVisibleForRole(toolInfo);
-      if (!isRoleAllowed) {
-        toolInfo.visible = false;
-        toolInfo.visibilityReason = 'role_restricted';
-        roleFilteredCount++;
-        continue;
-      }
+    for (const [name, visibility] of this.computeVisibility(this.currentRole)) {
+      const toolInfo = this.allTools.get(name)!;
+      toolInfo.visible = visibility.visible;
+      toolInfo.visibilityReason = visibility.reason;
 
-      // Step 2: Skill-based filtering (if enabled)
-      if (skillAllowedTools !== null) {
-        const isSkillAllowed = this.isToolAllowedBySkills(toolInfo.prefixedName, skillAllowedTools);
-        if (!isSkillAllowed) {
-          toolInfo.visible = false;
-          toolInfo.visibilityReason = 'skill_restricted';
-          skillFilteredCount++;
-          continue;
-        }
+      if (visibility.visible) {
+        this.visibleTools.set(name, toolInfo);
+      } else if (visibility.reason === 'skill_restricted') {
+        skillFilteredCount++;
+      } else if (visibility.reason !== 'backend_unavailable') {
+        roleFilteredCount++;
       }
-
-      // Tool is visible (passed both filters)
-      toolInfo.visible = true;
-      toolInfo.visibilityReason = this.skillFilteringEnabled ? 'role_and_skill_permitted' : 'role_permitted';
-      this.visibleTools.set(name, toolInfo);
     }
 
     this.logger.debug(
@@ -259,23 +430,141 @@ export class ToolVisibilityManager {
   }
 
   /**
-   * Check if a tool is visible for the current role (role-level only)
+   * Compute the visibility of every registered tool for a role
+   * Pure: does not change the current role or visible tools
    */
-  private isToolVisibleForRole(toolInfo: ToolInfo): boolean {
-    if (!this.currentRole) {
-      return true; // No role = show all
+  computeVisibility(role: Role | null): Map<string, ToolVisibility> {
+    const skillAllowedTools = this.skillFilteringEnabled ? this.getAllowedToolsFromSkills() : null;
+    const result = new Map<string, ToolVisibility>();
+
+    for (const [name, toolInfo] of this.allTools) {
+      result.set(name, this.decideVisibility(role, toolInfo, skillAllowedTools));
     }
 
+    return result;
+  }
+
+  /**
+   * Decide whether a role may use a tool, by the same rules checkAccess applies to the current role
+   * Pure: does not change the current role or visible tools.
+   * Tools that are not registered are judged on policy alone.
+   * @param serverName - Source server of an unregistered tool; defaults to its name prefix
+   */
+  decideToolAccess(role: Role | null, toolName: string, serverName?: string): ToolVisibility {
+    if (this.systemTools.isSystemTool(toolName)) {
+      return { visible: true, reason: 'system_tool' };
+    }
+
+    // Memory tools depend on the role's memory grant, not on tool permissions
+    if (ToolVisibilityManager.MEMORY_TOOLS.includes(toolName)) {
+      return role && this.roleManager.hasMemoryAccess(role.id)
+        ? { visible: true, reason: 'memory_granted' }
+        : { visible: false, reason: 'not_in_allow_list' };
+    }
+
+    const toolInfo = this.allTools.get(toolName) ?? {
+      prefixedName: toolName,
+      sourceServer: serverName ?? this.parseToolName(toolName).serverName
+    };
+    const skillAllowedTools = this.skillFilteringEnabled ? this.getAllowedToolsFromSkills() : null;
+    return this.decideVisibility(role, toolInfo, skillAllowedTools);
+  }
+
+  /**
+   * Visibility of one tool: system tools, backend availability, role rules, then skills
+   */
+  private decideVisibility(
+    role: Role | null,
+    toolInfo: Pick<ToolInfo, 'prefixedName' | 'sourceServer'>,
+    skillAllowedTools: Set<string> | null
+  ): ToolVisibility {
+    // System tools bypass role and skill filtering
+    if (this.systemTools.isSystemTool(toolInfo.prefixedName)) {
+      return { visible: true, reason: 'system_tool' };
+    }
+
+    // Step 0: Backend availability (not an RBAC decision)
+    if (this.unavailableServers.has(toolInfo.sourceServer)) {
+      return { visible: false, reason: 'backend_unavailable' };
+    }
+
+    // Step 1: Role-based filtering
+    const decision = this.getRoleDecision(role, toolInfo);
+    if (!decision.allowed) {
+      return { visible: false, reason: decision.reason };
+    }
+
+    // Step 2: Skill-based filtering (if enabled)
+    if (skillAllowedTools !== null &&
+        !this.isToolAllowedBySkills(this.resolveAlias(toolInfo.prefixedName), skillAllowedTools)) {
+      return { visible: false, reason: 'skill_restricted' };
+    }
+
+    // Tool is visible (passed both filters)
+    return { visible: true, reason: decision.reason };
+  }
+
+  /**
+   * List every registered tool with its visibility under the current role, sorted by name
+   * Read-only; intended for audits and compliance snapshots
+   */
+  snapshotVisibility(): ToolVisibilityEntry[] {
+    const visibility = this.computeVisibility(this.currentRole);
+    return [...visibility.entries()]
+      .sort(([a], [b]) => a.localeCompare(b))
+      .map(([name, entry]) => ({ name, ...entry }));
+  }
+
+  /**
+   * Compare which registered tools are visible to two roles
+   */
+  diffRoles(roleA: Role, roleB: Role): RoleToolDiff {
+    const visibleA = this.computeVisibility(roleA);
+    const visibleB = this.computeVisibility(roleB);
+    const diff: RoleToolDiff = { onlyA: [], onlyB: [], both: [] };
+
+    for (const name of [...this.allTools.keys()].sort()) {
+      const inA = visibleA.get(name)?.visible ?? false;
+      const inB = visibleB.get(name)?.visible ?? false;
+      if (inA && inB) diff.both.push(name);
+      else if (inA) diff.onlyA.push(name);
+      else if (inB) diff.onlyB.push(name);
+    }
+
+    return diff;
+  }
+
+  /**
+   * Decide whether a tool is visible for a role (role-level only)
+   */
+  private getRoleDecision(
+    role: Role | null,
+    toolInfo: Pick<ToolInfo, 'prefixedName' | 'sourceServer'>
+  ): ToolAccessDecision {
+    if (!role) {
+      return { allowed: true, reason: 'no_role' }; // No role = show all
+    }
+
+    // Aliased tools are checked under their canonical name, but a deny on the alias still applies
+    const toolName = this.resolveAlias(toolInfo.prefixedName);
+    const isAlias = toolName !== toolInfo.prefixedName;
+    if (isAlias) {
+      const aliasDenied = this.roleManager.getToolDenyDecision(role.id, toolInfo.prefixedName);
+      if (aliasDenied) return aliasDenied;
+    }
+    const serverName = isAlias ? this.parseToolName(toolName).serverName : toolInfo.sourceServer;
+
     // Check server access first
-    if (!this.isServerAllowed(toolInfo.sourceServer)) {
-      return false;
+    if (!this.isServerAllowedForRole(role, serverName)) {
+      return { allowed: false, reason: 'server_not_allowed' };
     }
 
     // Check tool-level permissions via RoleManager
-    return this.roleManager.isToolAllowedForRole(
-      this.currentRole.id,
-      toolInfo.prefixedName,
-      toolInfo.sourceServer
+    return this.roleManager.getToolAccessDecision(
+      role.id,
+      toolName,
+      serverName,
+      this.patternResolution
     );
   }
 
@@ -307,19 +596,15 @@ export class ToolVisibilityManager {
   }
 
   /**
-   * Check if a server is allowed for current role
+   * Check if a server is allowed for a role
    */
-  private isServerAllowed(serverName: string): boolean {
-    if (!this.currentRole) {
-      return true;
-    }
-
+  private isServerAllowedForRole(role: Role, serverName: string): boolean {
     // Wildcard allows all servers
-    if (this.currentRole.allowedServers.includes('*')) {
+    if (role.allowedServers.includes('*')) {
       return true;
     }
 
-    return this.currentRole.allowedServers.includes(serverName);
+    return role.allowedServers.includes(serverName);
   }
 
   /**
@@ -449,7 +734,7 @@ export class ToolVisibilityManager {
         sourceServer: 'mycelium-router',
         prefixedName: name,
         visible: true,
-        visibilityReason: `memory_granted:${memoryPermission.policy}`
+        visibilityReason: 'memory_granted'
       };
       this.visibleTools.set(name, toolInfo);
     }
@@ -464,10 +749,21 @@ export class ToolVisibilityManager {
   // Memory tools (only visible if role has memory permission)
   private static readonly MEMORY_TOOLS = ['save_memory', 'recall_memory', 'list_memories'];
 
+  /**
+   * Get the registry of always-visible system tools
+   */
+  getSystemTools(): SystemTools {
+    return this.systemTools;
+  }
+
   /**
    * Check if a tool is accessible (throws if not)
    */
   checkAccess(toolName: string): void {
+    if (this.systemTools.isSystemTool(toolName)) {
+      return;
+    }
+
     // Memory tools require memory permission
     if (ToolVisibilityManager.MEMORY_TOOLS.includes(toolName)) {
       const roleId = this.currentRole?.id;
@@ -483,8 +779,11 @@ export class ToolVisibilityManager {
 
     if (!this.visibleTools.has(toolName)) {
       const roleId = this.currentRole?.id || 'none';
-      throw new Error(
-        `Tool '${toolName}' is not accessible for role '${roleId}'. Check available tools for your skill.`
+      throw new ToolNotAccessibleError(
+        toolName,
+        roleId,
+        'Check available tools for your skill.',
+        this.allTools.get(toolName)?.visibilityReason
       );
     }
   }
@@ -493,6 +792,10 @@ export class ToolVisibilityManager {
    * Check if a tool is visible (returns boolean)
    */
   isVisible(toolName: string): boolean {
+    if (this.systemTools.isSystemTool(toolName)) {
+      return true;
+    }
+
     // Memory tools are visible only if role has memory permission
     if (ToolVisibilityManager.MEMORY_TOOLS.includes(toolName)) {
       const roleId = this.currentRole?.id;
@@ -32,6 +32,9 @@ export {
   type FetchPromptResult
 } from './remote-prompt-fetcher.js';
 
+// Serialized access for concurrent handlers
+export { SharedRouter, createSharedRouter } from './shared-router.js';
+
 // Router adapter for proxy integration
 export { RouterAdapter, createRouterAdapter } from './router-adapter.js';
 
@@ -8,8 +8,8 @@ import { promises as fs } from 'fs';
 import { homedir } from 'os';
 import { join } from 'path';
 import { Logger } from '../utils/logger.js';
-import { StdioRouter, type UpstreamServerInfo } from '../mcp/stdio-router.js';
-import { RoleManager, createRoleManager, ToolVisibilityManager, createToolVisibilityManager, RoleMemoryStore, createRoleMemoryStore, type MemoryEntry, type SaveMemoryOptions, type MemorySearchOptions } from '../rbac/index.js';
+import { StdioRouter, type UpstreamServerInfo, type ServerStartReport } from '../mcp/stdio-router.js';
+import { RoleManager, createRoleManager, ToolVisibilityManager, createToolVisibilityManager, RoleMemoryStore, createRoleMemoryStore, type MemoryEntry, type SaveMemoryOptions, type MemorySearchOptions, type ToolAccessDecision } from '../rbac/index.js';
 import type {
   Role,
   ToolInfo,
@@ -17,8 +17,10 @@ import type {
   SkillManifest,
   MCPServerConfig,
   BaseSkillDefinition,
-  SkillDefinition
+  SkillDefinition,
+  SystemTools
 } from '@mycelium/shared';
+import { ConfigValidationError, MemoryEscalationError, RoleOutsideTimeWindowError, RoleSwitchCooldownError, getRoleTimeWindow, isWithinTimeWindow, memoryPolicyRank, validateDesktopConfig } from '@mycelium/shared';
 import type {
   MyceliumRouterState,
   SubServerInfo,
@@ -26,7 +28,9 @@ import type {
   ManifestTool,
   RoleSwitchEvent,
   ToolsChangedEvent,
-  SetRoleOptions
+  SetRoleOptions,
+  RouterSnapshot,
+  MemoryEscalationPolicy
 } from '../types/router-types.js';
 import type { Tool } from '@modelcontextprotocol/sdk/types.js';
 import { v4 as uuidv4 } from 'uuid';
@@ -142,6 +146,18 @@ export class MyceliumCore extends EventEmitter {
   // Skill definitions for session-based filtering
   private skillDefinitions: Map<string, SkillDefinition> = new Map();
 
+  // Whether role switches may raise the memory policy
+  private memoryEscalationPolicy: MemoryEscalationPolicy;
+
+  // Minimum interval between role switches (0 = no limit)
+  private roleSwitchCooldownMs: number;
+
+  // Clock in epoch milliseconds (injectable for tests)
+  private now: () => number;
+
+  // When the last successful role switch happened, per the clock
+  private lastRoleSwitchAt: number | null = null;
+
   constructor(
     logger: Logger,
     options?: {
@@ -149,10 +165,16 @@ export class MyceliumCore extends EventEmitter {
       configFile?: string;
       memoryDir?: string;
       cwd?: string;
+      memoryEscalationPolicy?: MemoryEscalationPolicy;
+      roleSwitchCooldownMs?: number;
+      now?: () => number;
     }
   ) {
     super();
     this.logger = logger;
+    this.memoryEscalationPolicy = options?.memoryEscalationPolicy ?? 'allow';
+    this.roleSwitchCooldownMs = options?.roleSwitchCooldownMs ?? 0;
+    this.now = options?.now ?? Date.now;
 
     // Initialize StdioRouter for managing upstream servers
     this.stdioRouter = new StdioRouter(logger, { cwd: options?.cwd });
@@ -164,7 +186,7 @@ export class MyceliumCore extends EventEmitter {
     this.toolVisibility = createToolVisibilityManager(logger, this.roleManager);
 
     // Initialize role memory store
-    this.memoryStore = createRoleMemoryStore(options?.memoryDir || './memory');
+    this.memoryStore = createRoleMemoryStore(options?.memoryDir || './memory', this.roleManager, logger);
 
     // Initialize state
     this.state = {
@@ -248,8 +270,14 @@ export class MyceliumCore extends EventEmitter {
 
   /**
    * Load servers from Claude Desktop config format
+   * @throws ConfigValidationError if the config is invalid
    */
   loadServersFromConfig(config: { mcpServers: Record<string, MCPServerConfig> }): void {
+    const issues = validateDesktopConfig(config);
+    if (issues.length > 0) {
+      throw new ConfigValidationError(issues);
+    }
+
     this.stdioRouter.loadServersFromDesktopConfig(config);
     this.logger.info(`Loaded ${Object.keys(config.mcpServers).length} server configurations`);
   }
@@ -257,9 +285,10 @@ export class MyceliumCore extends EventEmitter {
   /**
    * Start all configured servers
    */
-  async startServers(): Promise<void> {
+  async startServers(): Promise<ServerStartReport> {
     this.logger.info('Starting upstream MCP servers...');
-    await this.stdioRouter.startServers();
+    const report = await this.stdioRouter.startServers();
+    this.applyServerStartReport(report);
 
     // Update connected servers state
     await this.updateConnectedServersState();
@@ -271,6 +300,7 @@ export class MyceliumCore extends EventEmitter {
     this.toolVisibility.setCurrentRole(this.state.currentRole);
 
     this.logger.info('All upstream servers started and tools discovered');
+    return report;
   }
 
   /**
@@ -317,44 +347,7 @@ export class MyceliumCore extends EventEmitter {
         return false;
       }
 
-      // Load roles from skill manifest
-      await this.roleManager.loadFromSkillManifest(skillManifest);
-
-      // Store skill definitions for session-based filtering
-      this.skillDefinitions.clear();
-      for (const skill of skillManifest.skills) {
-        this.skillDefinitions.set(skill.id, skill as SkillDefinition);
-      }
-      this.logger.info(`Stored ${this.skillDefinitions.size} skill definitions`);
-
-      // Load skill definitions into ToolVisibilityManager for skill-based filtering
-      this.toolVisibility.loadSkillDefinitions(skillManifest.skills as SkillDefinition[]);
-
-      // Update state with new roles
-      this.state.availableRoles.clear();
-      const allRoles = this.roleManager.getAllRoles();
-      for (const role of allRoles) {
-        this.state.availableRoles.set(role.id, role);
-      }
-
-      // Re-register ROUTER_TOOLS now that roles are loaded
-      // (discoverAllTools was called before roles were loaded, so ROUTER_TOOLS may have been skipped)
-      const skillDefinedRouterTools = ROUTER_TOOLS.filter(
-        tool => this.roleManager.isToolDefinedInAnySkill(tool.name)
-      );
-      if (skillDefinedRouterTools.length > 0) {
-        this.toolVisibility.registerTools(skillDefinedRouterTools, 'mycelium-router');
-        this.logger.info(`Registered ${skillDefinedRouterTools.length} router tools after loading roles`);
-      }
-
-      // Set default role and apply tool filtering
-      const defaultRole = this.roleManager.getDefaultRole();
-      if (defaultRole) {
-        this.state.currentRole = defaultRole;
-        // Apply tool visibility filtering based on new role
-        this.toolVisibility.setCurrentRole(defaultRole);
-        this.logger.info(`Applied tool filtering for default role: ${defaultRole.id}`);
-      }
+      await this.applySkillManifest(skillManifest);
 
       this.logger.info(`✅ Loaded ${this.state.availableRoles.size} roles from ${skillManifest.skills.length} skills`);
       return true;
@@ -365,6 +358,46 @@ export class MyceliumCore extends EventEmitter {
     }
   }
 
+  /**
+   * Load roles and skill definitions from a skill manifest and apply the default role
+   */
+  private async applySkillManifest(skillManifest: SkillManifest): Promise<void> {
+    // Load roles from skill manifest
+    await this.roleManager.loadFromSkillManifest(skillManifest);
+
+    // Store skill definitions for session-based filtering
+    this.skillDefinitions.clear();
+    for (const skill of skillManifest.skills) {
+      this.skillDefinitions.set(skill.id, skill as SkillDefinition);
+    }
+    this.logger.info(`Stored ${this.skillDefinitions.size} skill definitions`);
+
+    // Load skill definitions into ToolVisibilityManager for skill-based filtering
+    this.toolVisibility.loadSkillDefinitions(skillManifest.skills as SkillDefinition[]);
+
+    // Update state with new roles
+    this.refreshAvailableRoles();
+
+    // Re-register ROUTER_TOOLS now that roles are loaded
+    // (discoverAllTools was called before roles were loaded, so ROUTER_TOOLS may have been skipped)
+    const skillDefinedRouterTools = ROUTER_TOOLS.filter(
+      tool => this.roleManager.isToolDefinedInAnySkill(tool.name)
+    );
+    if (skillDefinedRouterTools.length > 0) {
+      this.toolVisibility.registerTools(skillDefinedRouterTools, 'mycelium-router');
+      this.logger.info(`Registered ${skillDefinedRouterTools.length} router tools after loading roles`);
+    }
+
+    // Set default role and apply tool filtering
+    const defaultRole = this.roleManager.getDefaultRole();
+    if (defaultRole) {
+      this.state.currentRole = defaultRole;
+      // Apply tool visibility filtering based on new role
+      this.toolVisibility.setCurrentRole(defaultRole);
+      this.logger.info(`Applied tool filtering for default role: ${defaultRole.id}`);
+    }
+  }
+
   /**
    * Transform skills data from mycelium-skills to BaseSkillDefinition format
    */
@@ -388,6 +421,7 @@ export class MyceliumCore extends EventEmitter {
           memory: skill.grants.memory,
           memoryTeamRoles: skill.grants.memoryTeamRoles
         } : undefined,
+        conflicts: skill.conflicts,
         metadata: {
           version: skill.version,
           category: skill.category,
@@ -493,12 +527,14 @@ export class MyceliumCore extends EventEmitter {
 
   /**
    * Start servers required for a specific role (lazy loading)
+   * Best-effort: the role keeps working with whichever servers started.
+   * Tools of failed servers are hidden as unavailable, not as RBAC denials.
    */
-  async startServersForRole(roleId: string): Promise<void> {
+  async startServersForRole(roleId: string): Promise<ServerStartReport> {
     const role = this.roleManager.getRole(roleId);
     if (!role) {
       this.logger.warn(`Role not found: ${roleId}`);
-      return;
+      return { started: [], failed: [] };
     }
 
     // Get allowed servers for this role
@@ -507,13 +543,19 @@ export class MyceliumCore extends EventEmitter {
     // If wildcard, start all servers
     if (allowedServers.includes('*')) {
       this.logger.info(`Role ${roleId} allows all servers, starting all...`);
-      await this.startServers();
-      return;
+      return await this.startServers();
     }
 
     // Start only the required servers
     this.logger.info(`Starting servers for role ${roleId}: ${allowedServers.join(', ')}`);
-    await this.stdioRouter.startServersByName(allowedServers);
+    const report = await this.stdioRouter.startServersByName(allowedServers);
+    this.applyServerStartReport(report);
+
+    if (report.failed.length > 0) {
+      this.logger.warn(`Some servers failed to start for role ${roleId}`, {
+        failed: report.failed.map(f => f.name)
+      });
+    }
 
     // Update state and discover tools
     await this.updateConnectedServersState();
@@ -522,6 +564,19 @@ export class MyceliumCore extends EventEmitter {
     // because currentRole hasn't been updated yet
 
     this.logger.info(`Servers started for role ${roleId}`);
+    return report;
+  }
+
+  /**
+   * Record server availability from a startup report
+   */
+  private applyServerStartReport(report: ServerStartReport): void {
+    for (const name of report.started) {
+      this.toolVisibility.setServerAvailability(name, true);
+    }
+    for (const { name } of report.failed) {
+      this.toolVisibility.setServerAvailability(name, false);
+    }
   }
 
   /**
@@ -654,10 +709,10 @@ export class MyceliumCore extends EventEmitter {
 
     this.logger.info(`🔄 Role switch requested: ${roleId}`);
 
-    // Validate role exists
-    const role = this.state.availableRoles.get(roleId);
+    // Validate role exists (ask the RoleManager so roles edited through getRoleManager() are current)
+    const role = this.roleManager.getRole(roleId);
     if (!role) {
-      const availableRoles = Array.from(this.state.availableRoles.keys());
+      const availableRoles = this.roleManager.getRoleIds();
       throw new Error(
         `Role '${roleId}' not found. Available roles: ${availableRoles.join(', ')}`
       );
@@ -666,9 +721,18 @@ export class MyceliumCore extends EventEmitter {
     // Track previous role for notifications
     const previousRole = this.state.currentRole;
 
+    if (previousRole && this.memoryEscalationPolicy === 'deny_upgrade') {
+      this.checkMemoryEscalation(previousRole.id, role.id);
+    }
+
+    const switchedAt = this.now();
+    this.checkRoleSwitchCooldown(previousRole?.id ?? null, role.id, switchedAt);
+    this.checkRoleTimeWindow(role, switchedAt);
+
     // Update current role
     this.state.currentRole = role;
-    this.state.metadata.lastRoleSwitch = new Date();
+    this.state.metadata.lastRoleSwitch = new Date(switchedAt);
+    this.lastRoleSwitchAt = switchedAt;
     this.state.metadata.roleSwitchCount++;
 
     // Update server activation status
@@ -702,6 +766,9 @@ export class MyceliumCore extends EventEmitter {
     await this.exportSessionState();
 
     this.logger.info(`✅ Role activated: ${role.name}`, {
+      event: 'role_switch',
+      roleId: role.id,
+      previousRoleId: previousRole?.id ?? null,
       toolCount: manifest.availableTools.length,
       serverCount: manifest.availableServers.length
     });
@@ -709,6 +776,66 @@ export class MyceliumCore extends EventEmitter {
     return manifest;
   }
 
+  /**
+   * Reject a role switch that raises the effective memory policy
+   * @throws MemoryEscalationError if the target role's policy is higher
+   */
+  private checkMemoryEscalation(fromRoleId: string, toRoleId: string): void {
+    const fromPolicy = this.roleManager.getEffectiveMemoryPermission(fromRoleId).policy;
+    const toPolicy = this.roleManager.getEffectiveMemoryPermission(toRoleId).policy;
+
+    if (memoryPolicyRank(toPolicy) > memoryPolicyRank(fromPolicy)) {
+      this.logger.warn(`Role switch denied: ${fromRoleId} → ${toRoleId} raises memory access`, {
+        event: 'role_switch_denied',
+        roleId: toRoleId,
+        previousRoleId: fromRoleId,
+        fromPolicy,
+        toPolicy
+      });
+      throw new MemoryEscalationError(fromRoleId, toRoleId, fromPolicy, toPolicy);
+    }
+  }
+
+  /**
+   * Reject a role switch that comes too soon after the previous one
+   * @throws RoleSwitchCooldownError if the cooldown has not elapsed
+   */
+  private checkRoleSwitchCooldown(fromRoleId: string | null, toRoleId: string, at: number): void {
+    if (this.roleSwitchCooldownMs <= 0 || this.lastRoleSwitchAt === null) {
+      return;
+    }
+
+    const retryAfterMs = this.lastRoleSwitchAt + this.roleSwitchCooldownMs - at;
+    if (retryAfterMs > 0) {
+      this.logger.warn(`Role switch denied: ${fromRoleId ?? '(none)'} → ${toRoleId} within cooldown`, {
+        event: 'role_switch_denied',
+        roleId: toRoleId,
+        previousRoleId: fromRoleId,
+        retryAfterMs
+      });
+      throw new RoleSwitchCooldownError(toRoleId, retryAfterMs);
+    }
+  }
+
+  /**
+   * Reject activating a role outside its metadata.allowedTime window
+   * @throws RoleOutsideTimeWindowError if the switch time is outside the window
+   */
+  private checkRoleTimeWindow(role: Role, at: number): void {
+    const window = getRoleTimeWindow(role);
+    if (!window || isWithinTimeWindow(window, new Date(at))) {
+      return;
+    }
+
+    const allowedTime = role.metadata?.allowedTime ?? '';
+    this.logger.warn(`Role switch denied: ${role.id} outside ${allowedTime}`, {
+      event: 'role_switch_denied',
+      roleId: role.id,
+      allowedTime
+    });
+    throw new RoleOutsideTimeWindowError(role.id, allowedTime);
+  }
+
   /**
    * Build the agent manifest for a role
    */
@@ -882,7 +1009,7 @@ export class MyceliumCore extends EventEmitter {
         throw new Error('content is required');
       }
 
-      const entry = await this.memoryStore.addEntry(roleId, content, {
+      const entry = await this.memoryStore.writeAs(roleId, roleId, content, {
         type: type || 'context',
         tags: tags ? (Array.isArray(tags) ? tags : [tags]) : undefined,
         source: source || 'agent'
@@ -926,7 +1053,7 @@ export class MyceliumCore extends EventEmitter {
 
       // Roles with 'all' policy can search across all roles
       if (canAccessAll && all_roles) {
-        const entries = await this.memoryStore.searchAll({
+        const entries = await this.memoryStore.searchAllAs(roleId, {
           query,
           type,
           tags: tags ? (Array.isArray(tags) ? tags : [tags]) : undefined,
@@ -965,7 +1092,7 @@ export class MyceliumCore extends EventEmitter {
       }
 
       // Normal search for current role
-      const entries = await this.memoryStore.search(roleId, {
+      const entries = await this.memoryStore.readAs(roleId, roleId, {
         query,
         type,
         tags: tags ? (Array.isArray(tags) ? tags : [tags]) : undefined,
@@ -1122,6 +1249,14 @@ export class MyceliumCore extends EventEmitter {
     };
   }
 
+  /**
+   * Get the registry of always-visible system tools
+   * Tools registered here skip role and skill checks
+   */
+  getSystemTools(): SystemTools {
+    return this.toolVisibility.getSystemTools();
+  }
+
   /**
    * Check if a tool is accessible for the current role
    * Throws an error if access is denied
@@ -1131,6 +1266,20 @@ export class MyceliumCore extends EventEmitter {
     this.toolVisibility.checkAccess(toolName);
   }
 
+  /**
+   * Simulate a role's access to a tool without switching roles
+   * Uses the same rules as checkToolAccess (aliases, system tools, backend availability, active skills).
+   * Dry run: does not change the current role or visible tools, and denials are not logged
+   * @param serverName - For tools not yet discovered; defaults to the server prefix of the tool name
+   */
+  simulateToolAccess(roleId: string, toolName: string, serverName?: string): ToolAccessDecision {
+    const role = this.roleManager.getRole(roleId);
+    if (!role) return { allowed: false, reason: 'no_role' };
+
+    const { visible, reason } = this.toolVisibility.decideToolAccess(role, toolName, serverName);
+    return { allowed: visible, reason };
+  }
+
   /**
    * Execute a tool call
    * @param toolName - The tool to execute
@@ -1140,8 +1289,27 @@ export class MyceliumCore extends EventEmitter {
     toolName: string,
     args: Record<string, unknown>
   ): Promise<any> {
+    const roleId = this.state.currentRole?.id ?? null;
+
     // Check access (throws if denied)
-    this.checkToolAccess(toolName);
+    try {
+      this.checkToolAccess(toolName);
+    } catch (error) {
+      this.logger.warn(`Tool call denied: ${toolName}`, {
+        event: 'tool_denied',
+        roleId,
+        toolName,
+        reason: this.toolVisibility.getToolInfo(toolName)?.visibilityReason ?? null
+      });
+      throw error;
+    }
+
+    this.logger.debug(`Dispatching tool call: ${toolName}`, {
+      event: 'tool_dispatch',
+      roleId,
+      toolName,
+      server: this.toolVisibility.getToolInfo(toolName)?.sourceServer ?? null
+    });
 
     // Route the call
     return await this.routeToolCall(toolName, args);
@@ -1200,6 +1368,14 @@ export class MyceliumCore extends EventEmitter {
     return this.stdioRouter;
   }
 
+  /**
+   * Get the underlying RoleManager for direct access if needed
+   * Roles registered or edited here take effect on the next setRole
+   */
+  getRoleManager(): RoleManager {
+    return this.roleManager;
+  }
+
   /**
    * Reload roles from skill server
    */
@@ -1457,6 +1633,78 @@ export class MyceliumCore extends EventEmitter {
     }));
   }
 
+  // ============================================================================
+  // Snapshot / Restore (warm restarts)
+  // ============================================================================
+
+  /**
+   * Capture the router's non-transport state
+   * Server processes are not included; restart them after restoring
+   */
+  snapshot(): RouterSnapshot {
+    return {
+      version: 2,
+      currentRole: this.state.currentRole?.id ?? null,
+      skills: Array.from(this.skillDefinitions.values()),
+      roles: this.roleManager.snapshotRoles(),
+      defaultRole: this.roleManager.getDefaultRoleId(),
+      activeSkills: this.toolVisibility.getActiveSkills(),
+      roleSwitchCount: this.state.metadata.roleSwitchCount
+    };
+  }
+
+  /**
+   * Restore state captured by snapshot()
+   * Reloads the snapshot's skills and role definitions, then reapplies the role and active skills.
+   * Version 1 snapshots carry no roles, so roles are regenerated from the skills.
+   */
+  async restore(snapshot: RouterSnapshot): Promise<void> {
+    if (snapshot.version !== 1 && snapshot.version !== 2) {
+      throw new Error(`Unsupported router snapshot version: ${snapshot.version}`);
+    }
+
+    await this.applySkillManifest({
+      skills: snapshot.skills,
+      version: '1.0.0',
+      generatedAt: new Date()
+    });
+
+    if (snapshot.roles) {
+      this.roleManager.restoreRoles(snapshot.roles, snapshot.defaultRole);
+      this.refreshAvailableRoles();
+      const defaultRole = this.roleManager.getDefaultRole();
+      if (defaultRole) {
+        this.state.currentRole = defaultRole;
+        this.toolVisibility.setCurrentRole(defaultRole);
+      }
+    }
+
+    if (snapshot.currentRole) {
+      const role = this.state.availableRoles.get(snapshot.currentRole);
+      if (!role) {
+        throw new Error(`Role '${snapshot.currentRole}' from snapshot not found`);
+      }
+      this.state.currentRole = role;
+      this.toolVisibility.setCurrentRole(role);
+    }
+
+    const activeSkills = snapshot.activeSkills.filter(id => this.skillDefinitions.has(id));
+    this.toolVisibility.setActiveSkills(activeSkills);
+    this.state.metadata.roleSwitchCount = snapshot.roleSwitchCount;
+
+    this.logger.info(`Restored router snapshot (role: ${snapshot.currentRole ?? 'none'})`);
+  }
+
+  /**
+   * Mirror the RoleManager's roles into router state
+   */
+  private refreshAvailableRoles(): void {
+    this.state.availableRoles.clear();
+    for (const role of this.roleManager.getAllRoles()) {
+      this.state.availableRoles.set(role.id, role);
+    }
+  }
+
   // ============================================================================
   // Session State Export (for Claude Code Hooks integration)
   // ============================================================================
@@ -1528,6 +1776,9 @@ export function createMyceliumCore(
     configFile?: string;
     memoryDir?: string;
     cwd?: string;
+    memoryEscalationPolicy?: MemoryEscalationPolicy;
+    roleSwitchCooldownMs?: number;
+    now?: () => number;
   }
 ): MyceliumCore {
   return new MyceliumCore(logger, options);
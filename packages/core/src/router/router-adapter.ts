@@ -5,6 +5,7 @@
 
 import { Logger } from '../utils/logger.js';
 import { MyceliumCore, createMyceliumCore } from './mycelium-core.js';
+import type { ServerStartReport } from '../mcp/stdio-router.js';
 import type { MCPServerConfig, ListRolesResult } from '@mycelium/shared';
 import type { Tool } from '@modelcontextprotocol/sdk/types.js';
 
@@ -92,9 +93,10 @@ export class RouterAdapter {
 
   /**
    * Start all servers
+   * Best-effort: returns which servers started and which failed
    */
-  async startServers(): Promise<void> {
-    await this.routerCore.startServers();
+  async startServers(): Promise<ServerStartReport> {
+    return this.routerCore.startServers();
   }
 
   /**
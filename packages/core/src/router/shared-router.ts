@@ -0,0 +1,65 @@
+// ============================================================================
+// MYCELIUM Router - Shared Router
+// Serializes access to a MyceliumCore shared by concurrent request handlers
+// ============================================================================
+
+import type { MyceliumCore } from './mycelium-core.js';
+import type { AgentManifest, SetRoleOptions } from '../types/router-types.js';
+
+/**
+ * Wraps a MyceliumCore so that operations run one at a time
+ *
+ * MyceliumCore methods such as setRole await between state updates, so two
+ * handlers interleaving a role switch and a tool read can observe each other's
+ * half-applied state. Every operation here is queued behind the previous one;
+ * use run() to make a multi-step sequence atomic.
+ */
+export class SharedRouter {
+  private core: MyceliumCore;
+
+  // Tail of the operation queue
+  private queue: Promise<unknown> = Promise.resolve();
+
+  constructor(core: MyceliumCore) {
+    this.core = core;
+  }
+
+  /**
+   * Run an operation with exclusive access to the router
+   * A failing operation rejects its own promise without blocking later ones
+   */
+  run<T>(operation: (core: MyceliumCore) => T | Promise<T>): Promise<T> {
+    const result = this.queue.then(() => operation(this.core));
+    this.queue = result.catch(() => undefined);
+    return result;
+  }
+
+  /**
+   * Switch the active role
+   */
+  setRole(options: SetRoleOptions): Promise<AgentManifest> {
+    return this.run(core => core.setRole(options));
+  }
+
+  /**
+   * Check that a tool is accessible under the active role
+   * @throws ToolNotAccessibleError if it is not
+   */
+  checkToolAccess(toolName: string): Promise<void> {
+    return this.run(core => core.checkToolAccess(toolName));
+  }
+
+  /**
+   * Names of the tools visible under the active role
+   */
+  getVisibleTools(): Promise<string[]> {
+    return this.run(core => core.getContext().availableTools.map(tool => tool.name));
+  }
+}
+
+/**
+ * Create a SharedRouter around an existing router core
+ */
+export function createSharedRouter(core: MyceliumCore): SharedRouter {
+  return new SharedRouter(core);
+}
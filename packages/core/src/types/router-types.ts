@@ -17,6 +17,7 @@ import type {
   RoleManifest,
   BaseSkillDefinition as SkillDefinition
 } from '@mycelium/shared';
+import type { RoleSnapshot } from '../rbac/role-manager.js';
 
 // ============================================================================
 // Role Configuration File Format
@@ -210,6 +211,33 @@ export interface RouterStateMetadata {
   sessionId: string;
 }
 
+/**
+ * Serializable router state for warm restarts
+ * Live server connections are not included and must be restarted separately
+ */
+export interface RouterSnapshot {
+  /** Snapshot format version (1 has no roles and regenerates them from skills) */
+  version: 1 | 2;
+
+  /** Active role ID (null if none) */
+  currentRole: string | null;
+
+  /** Skill definitions the roles were generated from */
+  skills: SkillDefinition[];
+
+  /** Role definitions with memory permissions, including edits made after loading (version 2) */
+  roles?: RoleSnapshot[];
+
+  /** Default role ID (version 2) */
+  defaultRole?: string;
+
+  /** Session-active skills */
+  activeSkills: string[];
+
+  /** Number of role switches before the snapshot */
+  roleSwitchCount: number;
+}
+
 // ============================================================================
 // Agent Manifest (set_role result)
 // ============================================================================
@@ -339,6 +367,13 @@ export interface ToolsChangedEvent {
 // Utility Types
 // ============================================================================
 
+/**
+ * How role switches that raise the memory policy are handled
+ * - 'allow': Any switch is allowed (default)
+ * - 'deny_upgrade': Reject switches to a role with a higher effective memory policy
+ */
+export type MemoryEscalationPolicy = 'allow' | 'deny_upgrade';
+
 /**
  * Options for set_role tool
  */
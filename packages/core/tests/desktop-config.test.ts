@@ -0,0 +1,83 @@
+/**
+ * Unit tests for desktop config loading
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import { promises as fs } from 'fs';
+import path from 'path';
+import os from 'os';
+import {
+  getDesktopConfigPaths,
+  loadDesktopConfig,
+  loadDesktopConfigFromFile
+} from '../src/mcp/desktop-config.js';
+
+describe('desktop config loading', () => {
+  let dir: string;
+
+  beforeEach(async () => {
+    dir = await fs.mkdtemp(path.join(os.tmpdir(), 'mycelium-config-'));
+  });
+
+  afterEach(async () => {
+    await fs.rm(dir, { recursive: true, force: true });
+  });
+
+  async function writeConfig(name: string, content: unknown): Promise<string> {
+    const filePath = path.join(dir, name);
+    await fs.writeFile(filePath, JSON.stringify(content));
+    return filePath;
+  }
+
+  it('should parse servers under the camelCase key', async () => {
+    const filePath = await writeConfig('config.json', {
+      mcpServers: {
+        filesystem: { command: 'npx', args: ['-y', '@modelcontextprotocol/server-filesystem'] }
+      }
+    });
+
+    const config = await loadDesktopConfigFromFile(filePath);
+
+    expect(config.mcpServers.filesystem).toEqual({
+      command: 'npx',
+      args: ['-y', '@modelcontextprotocol/server-filesystem']
+    });
+  });
+
+  it('should parse servers under the snake_case key', async () => {
+    const filePath = await writeConfig('config.json', {
+      mcp_servers: { git: { command: 'git-mcp' } }
+    });
+
+    const config = await loadDesktopConfigFromFile(filePath);
+
+    expect(Object.keys(config.mcpServers)).toEqual(['git']);
+  });
+
+  it('should load the first existing config from the search paths', async () => {
+    const second = await writeConfig('second.json', { mcpServers: { second: { command: 'b' } } });
+
+    const config = await loadDesktopConfig([path.join(dir, 'missing.json'), second]);
+
+    expect(Object.keys(config.mcpServers)).toEqual(['second']);
+  });
+
+  it('should list the searched paths when no config exists', async () => {
+    await expect(loadDesktopConfig([path.join(dir, 'missing.json')]))
+      .rejects.toThrow('No desktop config found');
+  });
+
+  it('should search the explicit path, XDG config, then Claude Desktop config', () => {
+    const paths = getDesktopConfigPaths(
+      { MYCELIUM_CONFIG_PATH: '/etc/mycelium.json', XDG_CONFIG_HOME: '/xdg' },
+      'linux',
+      '/home/user'
+    );
+
+    expect(paths).toEqual([
+      '/etc/mycelium.json',
+      path.join('/xdg', 'mycelium', 'config.json'),
+      path.join('/xdg', 'Claude', 'claude_desktop_config.json')
+    ]);
+  });
+});
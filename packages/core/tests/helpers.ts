@@ -0,0 +1,50 @@
+/**
+ * Shared fixtures for core tests
+ */
+
+import type { Logger, BaseSkillDefinition, Role, SkillManifest } from '@mycelium/shared';
+import type { Tool } from '@modelcontextprotocol/sdk/types.js';
+
+// Silent test logger
+export const testLogger: Logger = {
+  debug: () => {},
+  info: () => {},
+  warn: () => {},
+  error: () => {}
+};
+
+export function createSkill(overrides: Partial<BaseSkillDefinition> & { id: string }): BaseSkillDefinition {
+  return {
+    displayName: overrides.id,
+    description: `${overrides.id} skill`,
+    allowedRoles: [],
+    allowedTools: [],
+    ...overrides
+  };
+}
+
+export function createRole(overrides: Partial<Role> & { id: string }): Role {
+  return {
+    name: overrides.id,
+    description: `${overrides.id} role`,
+    allowedServers: ['*'],
+    systemInstruction: '',
+    ...overrides
+  };
+}
+
+export function createManifest(skills: BaseSkillDefinition[]): SkillManifest {
+  return {
+    skills,
+    version: '1.0.0',
+    generatedAt: new Date()
+  };
+}
+
+export function createTool(name: string): Tool {
+  return {
+    name,
+    description: `${name} tool`,
+    inputSchema: { type: 'object', properties: {} }
+  };
+}
@@ -5,8 +5,9 @@
  * using real implementations (not mocks) to ensure proper integration.
  */
 
-import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import { describe, it, expect, vi, beforeEach, afterEach } from 'vitest';
 import { MyceliumCore, createMyceliumCore } from '../src/router/mycelium-core.js';
+import { ConfigValidationError, MemoryEscalationError, RoleOutsideTimeWindowError, RoleSwitchCooldownError } from '@mycelium/shared';
 import type { Logger } from '@mycelium/shared';
 
 // Test logger that silences output
@@ -111,6 +112,16 @@ describe('MyceliumCore', () => {
         });
       }).not.toThrow();
     });
+
+    it('should reject invalid server configs', () => {
+      expect(() => {
+        router.loadServersFromConfig({
+          mcpServers: {
+            'my__server': { command: 'node', args: ['s1.js'] }
+          }
+        });
+      }).toThrow(ConfigValidationError);
+    });
   });
 
   describe('listRoles', () => {
@@ -172,3 +183,440 @@ describe('MyceliumCore Error Handling', () => {
       .rejects.toThrow(/not found/);
   });
 });
+
+describe('MyceliumCore partial startup', () => {
+  const skills = [
+    {
+      id: 'data-access',
+      displayName: 'Data Access',
+      description: 'Read files and query the database',
+      allowedRoles: ['developer'],
+      allowedTools: ['alpha__read', 'beta__query']
+    }
+  ];
+
+  const catalog = [
+    { name: 'alpha__read', inputSchema: { type: 'object', properties: {} } },
+    { name: 'beta__query', inputSchema: { type: 'object', properties: {} } }
+  ];
+
+  function stubStdioRouter(router: MyceliumCore) {
+    const stdio = router.getStdioRouter();
+    vi.spyOn(stdio, 'routeRequest').mockImplementation(async (request: any) => {
+      if (request.method === 'tools/list') {
+        return { result: { tools: catalog } };
+      }
+      return { result: { content: [{ type: 'text', text: JSON.stringify({ skills }) }] } };
+    });
+    vi.spyOn(stdio, 'startServersByName').mockResolvedValue({
+      started: ['alpha'],
+      failed: [{ name: 'beta', error: 'spawn beta-server ENOENT' }]
+    });
+    vi.spyOn(stdio, 'getAvailableServers').mockReturnValue([
+      { name: 'alpha', connected: true },
+      { name: 'beta', connected: false }
+    ]);
+    vi.spyOn(router, 'exportSessionState').mockResolvedValue();
+  }
+
+  it('should keep the role working when one of two servers fails', async () => {
+    const router = new MyceliumCore(testLogger);
+    stubStdioRouter(router);
+
+    await router.loadRolesFromSkillsServer();
+    const report = await router.startServersForRole('developer');
+
+    expect(report.started).toEqual(['alpha']);
+    expect(report.failed).toEqual([{ name: 'beta', error: 'spawn beta-server ENOENT' }]);
+
+    await router.setRole({ role: 'developer' });
+    const toolNames = router.getContext().availableTools.map(t => t.name);
+    expect(toolNames).toContain('alpha__read');
+    expect(toolNames).not.toContain('beta__query');
+  });
+});
+
+describe('MyceliumCore simulateToolAccess', () => {
+  const skills = [
+    {
+      id: 'read-only',
+      displayName: 'Read Only',
+      description: 'Read files',
+      allowedRoles: ['guest', 'admin'],
+      allowedTools: ['filesystem__read_file']
+    },
+    {
+      id: 'shell',
+      displayName: 'Shell',
+      description: 'Run shell commands',
+      allowedRoles: ['admin'],
+      allowedTools: ['shell__exec']
+    }
+  ];
+
+  async function createRouter(): Promise<MyceliumCore> {
+    const router = new MyceliumCore(testLogger);
+    vi.spyOn(router.getStdioRouter(), 'routeRequest').mockResolvedValue({
+      result: { content: [{ type: 'text', text: JSON.stringify({ skills }) }] }
+    });
+    await router.loadRolesFromSkillsServer();
+    return router;
+  }
+
+  it('should report decisions for roles other than the current one', async () => {
+    const router = await createRouter();
+
+    expect(router.simulateToolAccess('admin', 'shell__exec', 'shell'))
+      .toEqual({ allowed: true, reason: 'allowed_by_permission' });
+    expect(router.simulateToolAccess('guest', 'shell__exec'))
+      .toEqual({ allowed: false, reason: 'server_not_allowed' });
+    expect(router.simulateToolAccess('nobody', 'shell__exec'))
+      .toEqual({ allowed: false, reason: 'no_role' });
+  });
+
+  it('should not change the current role', async () => {
+    const router = await createRouter();
+    const before = router.getCurrentRole()?.id;
+
+    router.simulateToolAccess('admin', 'shell__exec');
+
+    expect(router.getCurrentRole()?.id).toBe(before);
+    expect(before).not.toBe('admin');
+  });
+
+  it('should agree with checkToolAccess for system tools', async () => {
+    const router = await createRouter();
+    vi.spyOn(router.getStdioRouter(), 'startServersByName').mockResolvedValue({ started: [], failed: [] });
+    vi.spyOn(router, 'exportSessionState').mockResolvedValue();
+    await router.setRole({ role: 'guest' });
+
+    expect(router.simulateToolAccess('guest', 'mycelium-router__get_context'))
+      .toEqual({ allowed: true, reason: 'system_tool' });
+    expect(() => router.checkToolAccess('mycelium-router__get_context')).not.toThrow();
+  });
+
+  it('should agree with checkToolAccess for tools of an unavailable server', async () => {
+    const router = new MyceliumCore(testLogger);
+    const stdio = router.getStdioRouter();
+    vi.spyOn(stdio, 'routeRequest').mockImplementation(async (request: any) => {
+      if (request.method === 'tools/list') {
+        return { result: { tools: [{ name: 'filesystem__read_file', inputSchema: { type: 'object', properties: {} } }] } };
+      }
+      return { result: { content: [{ type: 'text', text: JSON.stringify({ skills }) }] } };
+    });
+    vi.spyOn(stdio, 'startServersByName').mockResolvedValue({
+      started: [],
+      failed: [{ name: 'filesystem', error: 'spawn ENOENT' }]
+    });
+    vi.spyOn(stdio, 'getAvailableServers').mockReturnValue([{ name: 'filesystem', connected: false }]);
+    vi.spyOn(router, 'exportSessionState').mockResolvedValue();
+    await router.loadRolesFromSkillsServer();
+    await router.startServersForRole('guest');
+    await router.setRole({ role: 'guest' });
+
+    expect(router.simulateToolAccess('guest', 'filesystem__read_file'))
+      .toEqual({ allowed: false, reason: 'backend_unavailable' });
+    expect(() => router.checkToolAccess('filesystem__read_file')).toThrow();
+  });
+});
+
+describe('MyceliumCore structured logging', () => {
+  function createCapturingLogger() {
+    return {
+      debug: vi.fn(),
+      info: vi.fn(),
+      warn: vi.fn(),
+      error: vi.fn()
+    };
+  }
+
+  const skills = [
+    {
+      id: 'shell',
+      displayName: 'Shell',
+      description: 'Run shell commands',
+      allowedRoles: ['admin'],
+      allowedTools: ['shell__exec']
+    },
+    {
+      id: 'read-only',
+      displayName: 'Read Only',
+      description: 'Read files',
+      allowedRoles: ['guest'],
+      allowedTools: ['filesystem__read_file']
+    }
+  ];
+
+  async function createRouter(logger: Logger): Promise<MyceliumCore> {
+    const router = new MyceliumCore(logger);
+    vi.spyOn(router.getStdioRouter(), 'routeRequest').mockResolvedValue({
+      result: { content: [{ type: 'text', text: JSON.stringify({ skills }) }] }
+    });
+    vi.spyOn(router, 'exportSessionState').mockResolvedValue();
+    await router.loadRolesFromSkillsServer();
+    return router;
+  }
+
+  it('should emit a role_switch event with role ids', async () => {
+    const logger = createCapturingLogger();
+    const router = await createRouter(logger);
+    const previousRoleId = router.getCurrentRole()?.id ?? null;
+
+    await router.setRole({ role: 'guest' });
+
+    expect(logger.info).toHaveBeenCalledWith(
+      expect.stringContaining('Role activated'),
+      expect.objectContaining({ event: 'role_switch', roleId: 'guest', previousRoleId })
+    );
+  });
+
+  it('should emit a tool_denied event when a call is rejected', async () => {
+    const logger = createCapturingLogger();
+    const router = await createRouter(logger);
+    await router.setRole({ role: 'guest' });
+
+    await expect(router.executeToolCall('shell__exec', {})).rejects.toThrow();
+
+    expect(logger.warn).toHaveBeenCalledWith(
+      expect.stringContaining('shell__exec'),
+      expect.objectContaining({ event: 'tool_denied', roleId: 'guest', toolName: 'shell__exec' })
+    );
+  });
+});
+
+describe('MyceliumCore snapshot/restore', () => {
+  const skills = [
+    {
+      id: 'read-only',
+      displayName: 'Read Only',
+      description: 'Read files',
+      allowedRoles: ['guest', 'admin'],
+      allowedTools: ['filesystem__read_file']
+    },
+    {
+      id: 'shell',
+      displayName: 'Shell',
+      description: 'Run shell commands',
+      allowedRoles: ['admin'],
+      allowedTools: ['shell__exec']
+    }
+  ];
+
+  function createRouter(): MyceliumCore {
+    const router = new MyceliumCore(testLogger);
+    vi.spyOn(router, 'exportSessionState').mockResolvedValue();
+    return router;
+  }
+
+  it('should restore the current role and roles into a fresh router', async () => {
+    const original = createRouter();
+    vi.spyOn(original.getStdioRouter(), 'routeRequest').mockResolvedValue({
+      result: { content: [{ type: 'text', text: JSON.stringify({ skills }) }] }
+    });
+    await original.loadRolesFromSkillsServer();
+    await original.setRole({ role: 'admin' });
+
+    const snapshot = JSON.parse(JSON.stringify(original.snapshot()));
+
+    const restored = createRouter();
+    await restored.restore(snapshot);
+
+    expect(restored.getCurrentRole()?.id).toBe('admin');
+    expect(restored.listRoles().roles.map(r => r.id).sort())
+      .toEqual(original.listRoles().roles.map(r => r.id).sort());
+    expect(restored.getStateMetadata().roleSwitchCount).toBe(1);
+  });
+
+  it('should restore role edits and memory permissions made after loading', async () => {
+    const original = createRouter();
+    vi.spyOn(original.getStdioRouter(), 'routeRequest').mockResolvedValue({
+      result: { content: [{ type: 'text', text: JSON.stringify({ skills }) }] }
+    });
+    await original.loadRolesFromSkillsServer();
+    const roleManager = original.getRoleManager();
+    roleManager.updateRole('guest', role => {
+      role.toolPermissions = { ...role.toolPermissions, deny: ['filesystem__read_file'] };
+    });
+    roleManager.registerRole({
+      id: 'auditor',
+      name: 'Auditor',
+      description: 'Registered after loading',
+      allowedServers: ['filesystem'],
+      systemInstruction: ''
+    });
+    roleManager.setMemoryPermission('auditor', { policy: 'all' });
+    await original.setRole({ role: 'auditor' });
+
+    const snapshot = JSON.parse(JSON.stringify(original.snapshot()));
+
+    const restored = createRouter();
+    await restored.restore(snapshot);
+
+    expect(restored.getCurrentRole()?.id).toBe('auditor');
+    const restoredRoles = restored.getRoleManager();
+    expect(restoredRoles.getRole('guest')?.toolPermissions?.deny).toEqual(['filesystem__read_file']);
+    expect(restoredRoles.isToolAllowedForRole('guest', 'filesystem__read_file', 'filesystem')).toBe(false);
+    expect(restoredRoles.getRole('auditor')?.description).toBe('Registered after loading');
+    expect(restoredRoles.getMemoryPermission('auditor')).toEqual({ policy: 'all' });
+  });
+
+  it('should reject a snapshot whose role no longer exists', async () => {
+    const router = createRouter();
+
+    await expect(router.restore({
+      version: 1,
+      currentRole: 'ghost',
+      skills,
+      activeSkills: [],
+      roleSwitchCount: 0
+    })).rejects.toThrow("Role 'ghost' from snapshot not found");
+  });
+});
+
+describe('MyceliumCore memory escalation policy', () => {
+  // admin is listed first so it becomes the default role
+  const skills = [
+    {
+      id: 'admin-memory',
+      displayName: 'Admin Memory',
+      description: 'Access all memories',
+      allowedRoles: ['admin'],
+      allowedTools: ['shell__exec'],
+      grants: { memory: 'all' }
+    },
+    {
+      id: 'read-only',
+      displayName: 'Read Only',
+      description: 'Read files',
+      allowedRoles: ['admin', 'guest'],
+      allowedTools: ['filesystem__read_file']
+    }
+  ];
+
+  async function createRouter(memoryEscalationPolicy?: 'allow' | 'deny_upgrade'): Promise<MyceliumCore> {
+    const router = new MyceliumCore(testLogger, { memoryEscalationPolicy });
+    vi.spyOn(router.getStdioRouter(), 'routeRequest').mockResolvedValue({
+      result: { content: [{ type: 'text', text: JSON.stringify({ skills }) }] }
+    });
+    vi.spyOn(router, 'exportSessionState').mockResolvedValue();
+    await router.loadRolesFromSkillsServer();
+    return router;
+  }
+
+  it('should allow downgrading and reject upgrading under deny_upgrade', async () => {
+    const router = await createRouter('deny_upgrade');
+    expect(router.getCurrentRole()?.id).toBe('admin');
+
+    await router.setRole({ role: 'guest' });
+    expect(router.getCurrentRole()?.id).toBe('guest');
+
+    await expect(router.setRole({ role: 'admin' })).rejects.toThrow(MemoryEscalationError);
+    expect(router.getCurrentRole()?.id).toBe('guest');
+  });
+
+  it('should allow upgrading by default', async () => {
+    const router = await createRouter();
+
+    await router.setRole({ role: 'guest' });
+    await router.setRole({ role: 'admin' });
+
+    expect(router.getCurrentRole()?.id).toBe('admin');
+  });
+});
+
+describe('MyceliumCore role switch cooldown', () => {
+  const skills = [
+    {
+      id: 'read-only',
+      displayName: 'Read Only',
+      description: 'Read files',
+      allowedRoles: ['guest', 'admin'],
+      allowedTools: ['filesystem__read_file']
+    }
+  ];
+
+  let clock: number;
+
+  async function createRouter(roleSwitchCooldownMs?: number): Promise<MyceliumCore> {
+    const router = new MyceliumCore(testLogger, { roleSwitchCooldownMs, now: () => clock });
+    vi.spyOn(router.getStdioRouter(), 'routeRequest').mockResolvedValue({
+      result: { content: [{ type: 'text', text: JSON.stringify({ skills }) }] }
+    });
+    vi.spyOn(router, 'exportSessionState').mockResolvedValue();
+    await router.loadRolesFromSkillsServer();
+    return router;
+  }
+
+  beforeEach(() => {
+    clock = 1_000_000;
+  });
+
+  it('should reject a second switch within the cooldown', async () => {
+    const router = await createRouter(60_000);
+
+    await router.setRole({ role: 'admin' });
+    clock += 10_000;
+
+    const error = await router.setRole({ role: 'guest' }).catch(e => e);
+    expect(error).toBeInstanceOf(RoleSwitchCooldownError);
+    expect(error.retryAfterMs).toBe(50_000);
+    expect(router.getCurrentRole()?.id).toBe('admin');
+  });
+
+  it('should allow switches spaced beyond the cooldown', async () => {
+    const router = await createRouter(60_000);
+
+    await router.setRole({ role: 'admin' });
+    clock += 60_000;
+    await router.setRole({ role: 'guest' });
+
+    expect(router.getCurrentRole()?.id).toBe('guest');
+    expect(router.snapshot().roleSwitchCount).toBe(2);
+  });
+
+  it('should not limit switches without a cooldown', async () => {
+    const router = await createRouter();
+
+    await router.setRole({ role: 'admin' });
+    await router.setRole({ role: 'guest' });
+
+    expect(router.getCurrentRole()?.id).toBe('guest');
+  });
+});
+
+describe('MyceliumCore role time windows', () => {
+  let clock: number;
+
+  function createRouter(allowedTime: string): MyceliumCore {
+    const router = new MyceliumCore(testLogger, { now: () => clock });
+    vi.spyOn(router, 'exportSessionState').mockResolvedValue();
+    router.getRoleManager().registerRole({
+      id: 'night-ops',
+      name: 'Night Ops',
+      description: 'Only during the night shift',
+      allowedServers: ['*'],
+      systemInstruction: '',
+      metadata: { allowedTime }
+    });
+    return router;
+  }
+
+  it('should allow activating a role inside its window', async () => {
+    clock = new Date(2026, 0, 1, 23, 30).getTime();
+    const router = createRouter('22:00-06:00');
+
+    await router.setRole({ role: 'night-ops' });
+
+    expect(router.getCurrentRole()?.id).toBe('night-ops');
+  });
+
+  it('should reject activating a role outside its window', async () => {
+    clock = new Date(2026, 0, 1, 12, 0).getTime();
+    const router = createRouter('22:00-06:00');
+
+    const error = await router.setRole({ role: 'night-ops' }).catch(e => e);
+    expect(error).toBeInstanceOf(RoleOutsideTimeWindowError);
+    expect(error.window).toBe('22:00-06:00');
+    expect(router.getCurrentRole()).toBeNull();
+  });
+});
@@ -0,0 +1,575 @@
+/**
+ * Unit tests for RoleManager
+ */
+
+import { describe, it, expect, beforeEach } from 'vitest';
+import { RoleManager } from '../src/rbac/role-manager.js';
+import { InvalidPatternError, InvalidTimeWindowError, RoleValidationError, SkillConflictError } from '@mycelium/shared';
+import { testLogger, createSkill, createRole, createManifest } from './helpers.js';
+
+describe('RoleManager', () => {
+  let roleManager: RoleManager;
+
+  beforeEach(() => {
+    roleManager = new RoleManager(testLogger);
+  });
+
+  describe('getEffectiveMemoryPermissionTransitive', () => {
+    it('should resolve a three-level team chain', async () => {
+      await roleManager.loadFromSkillManifest(createManifest([
+        createSkill({
+          id: 'lead-memory',
+          allowedRoles: ['lead'],
+          allowedTools: ['filesystem__read_file'],
+          grants: { memory: 'team', memoryTeamRoles: ['sublead'] }
+        }),
+        createSkill({
+          id: 'sublead-memory',
+          allowedRoles: ['sublead'],
+          allowedTools: ['filesystem__read_file'],
+          grants: { memory: 'team', memoryTeamRoles: ['worker'] }
+        }),
+        createSkill({
+          id: 'worker-memory',
+          allowedRoles: ['worker'],
+          allowedTools: ['filesystem__read_file'],
+          grants: { memory: 'isolated' }
+        })
+      ]));
+
+      // Non-transitive stays the default
+      expect(roleManager.getEffectiveMemoryPermission('lead').teamRoles).toEqual(['sublead']);
+      expect(roleManager.canAccessRoleMemory('lead', 'worker')).toBe(false);
+
+      const transitive = roleManager.getEffectiveMemoryPermissionTransitive('lead');
+      expect(transitive.policy).toBe('team');
+      expect(transitive.teamRoles).toEqual(['sublead', 'worker']);
+    });
+
+    it('should stop at the configured depth', async () => {
+      await roleManager.loadFromSkillManifest(createManifest([
+        createSkill({
+          id: 'lead-memory',
+          allowedRoles: ['lead'],
+          grants: { memory: 'team', memoryTeamRoles: ['sublead'] }
+        }),
+        createSkill({
+          id: 'sublead-memory',
+          allowedRoles: ['sublead'],
+          grants: { memory: 'team', memoryTeamRoles: ['worker'] }
+        })
+      ]));
+
+      const shallow = roleManager.getEffectiveMemoryPermissionTransitive('lead', 1);
+      expect(shallow.teamRoles).toEqual(['sublead']);
+    });
+
+    it('should terminate on cyclic team definitions', async () => {
+      await roleManager.loadFromSkillManifest(createManifest([
+        createSkill({
+          id: 'alpha-memory',
+          allowedRoles: ['alpha'],
+          grants: { memory: 'team', memoryTeamRoles: ['beta'] }
+        }),
+        createSkill({
+          id: 'beta-memory',
+          allowedRoles: ['beta'],
+          grants: { memory: 'team', memoryTeamRoles: ['gamma'] }
+        }),
+        createSkill({
+          id: 'gamma-memory',
+          allowedRoles: ['gamma'],
+          grants: { memory: 'team', memoryTeamRoles: ['alpha'] }
+        })
+      ]));
+
+      const transitive = roleManager.getEffectiveMemoryPermissionTransitive('alpha', 10);
+      expect(transitive.teamRoles).toEqual(['beta', 'gamma']);
+    });
+
+    it('should return non-team policies unchanged', async () => {
+      await roleManager.loadFromSkillManifest(createManifest([
+        createSkill({
+          id: 'admin-memory',
+          allowedRoles: ['admin'],
+          grants: { memory: 'all' }
+        })
+      ]));
+
+      expect(roleManager.getEffectiveMemoryPermissionTransitive('admin')).toEqual({ policy: 'all' });
+      expect(roleManager.getEffectiveMemoryPermissionTransitive('unknown')).toEqual({ policy: 'none' });
+    });
+  });
+
+  describe('registerRoleValidated', () => {
+    it('should register a well-formed role', () => {
+      roleManager.registerRoleValidated(createRole({ id: 'developer', inherits: 'guest' }));
+      expect(roleManager.hasRole('developer')).toBe(true);
+    });
+
+    it('should reject an empty or whitespace id', () => {
+      expect(() => roleManager.registerRoleValidated(createRole({ id: '' }))).toThrow(RoleValidationError);
+      expect(() => roleManager.registerRoleValidated(createRole({ id: '   ' }))).toThrow('must not be empty');
+    });
+
+    it('should reject a self-inheriting role', () => {
+      expect(() => roleManager.registerRoleValidated(createRole({ id: 'loop', inherits: 'loop' })))
+        .toThrow('must not inherit from itself');
+    });
+
+    it('should reject an id containing a newline', () => {
+      try {
+        roleManager.registerRoleValidated(createRole({ id: 'admin\nguest' }));
+        expect.fail('registerRoleValidated should throw');
+      } catch (error) {
+        expect((error as RoleValidationError).issue).toBe('control_characters');
+      }
+      expect(roleManager.hasRole('admin\nguest')).toBe(false);
+    });
+
+    it('should accept a valid deny pattern', () => {
+      roleManager.registerRoleValidated(createRole({
+        id: 'reader',
+        toolPermissions: { allowPatterns: ['filesystem__*'], denyPatterns: ['*__delete_*'] }
+      }));
+      expect(roleManager.hasRole('reader')).toBe(true);
+    });
+
+    it('should reject a pattern with an unbalanced bracket', () => {
+      const role = createRole({ id: 'reader', toolPermissions: { denyPatterns: ['filesystem__[delete*'] } });
+
+      expect(() => roleManager.registerRoleValidated(role)).toThrow(InvalidPatternError);
+      expect(() => roleManager.registerRoleValidated(role)).toThrow("'[' is not supported");
+      expect(roleManager.hasRole('reader')).toBe(false);
+    });
+
+    it('should reject a malformed allowedTime window', () => {
+      const role = createRole({ id: 'night-ops', metadata: { allowedTime: '22:00-22:00' } });
+
+      expect(() => roleManager.registerRoleValidated(role)).toThrow(InvalidTimeWindowError);
+      expect(roleManager.hasRole('night-ops')).toBe(false);
+    });
+
+    it('should keep registerRole permissive', () => {
+      roleManager.registerRole(createRole({ id: 'loop', inherits: 'loop' }));
+      expect(roleManager.hasRole('loop')).toBe(true);
+    });
+
+    it('should still reject bad patterns in registerRole', () => {
+      const role = createRole({ id: 'reader', toolPermissions: { denyPatterns: ['filesystem__rea?'] } });
+
+      expect(() => roleManager.registerRole(role)).toThrow(InvalidPatternError);
+      expect(roleManager.hasRole('reader')).toBe(false);
+    });
+
+    it('should register none of a batch containing a bad pattern', () => {
+      expect(() => roleManager.registerRolesChecked([
+        createRole({ id: 'reader' }),
+        createRole({ id: 'writer', toolPermissions: { allowPatterns: ['filesystem__{write,edit}*'] } })
+      ])).toThrow(InvalidPatternError);
+      expect(roleManager.hasRole('reader')).toBe(false);
+    });
+
+    it('should reject a manifest with a bad allowedTools pattern and keep the loaded roles', async () => {
+      await roleManager.loadFromSkillManifest(createManifest([
+        createSkill({ id: 'reader', allowedRoles: ['reader'], allowedTools: ['filesystem__read_file'] })
+      ]));
+
+      await expect(roleManager.loadFromSkillManifest(createManifest([
+        createSkill({ id: 'writer', allowedRoles: ['writer'], allowedTools: ['filesystem__write file'] })
+      ]))).rejects.toThrow(InvalidPatternError);
+
+      expect(roleManager.getRoleIds()).toEqual(['reader']);
+    });
+  });
+
+  describe('effective permission cache', () => {
+    it('should recompute after a new role is registered', () => {
+      roleManager.registerRole(createRole({
+        id: 'developer',
+        inherits: 'base',
+        allowedServers: ['git'],
+        toolPermissions: { allow: ['git__commit'] }
+      }));
+
+      // Parent missing: only the role's own permissions
+      expect(roleManager.getEffectiveServers('developer')).toEqual(['git']);
+      expect(roleManager.getEffectiveToolPermissions('developer').allow).toEqual(['git__commit']);
+
+      roleManager.registerRole(createRole({
+        id: 'base',
+        allowedServers: ['filesystem'],
+        toolPermissions: { allow: ['filesystem__read_file'] }
+      }));
+
+      expect(roleManager.getEffectiveServers('developer')).toEqual(['git', 'filesystem']);
+      expect(roleManager.getEffectiveToolPermissions('developer').allow)
+        .toEqual(['filesystem__read_file', 'git__commit']);
+    });
+
+    it('should not let callers mutate cached results', () => {
+      roleManager.registerRole(createRole({ id: 'developer', toolPermissions: { deny: ['git__push'] } }));
+
+      roleManager.getEffectiveToolPermissions('developer').deny!.push('git__commit');
+      roleManager.getEffectiveServers('developer').push('filesystem');
+
+      expect(roleManager.getEffectiveToolPermissions('developer').deny).toEqual(['git__push']);
+      expect(roleManager.getEffectiveServers('developer')).toEqual(['*']);
+    });
+
+    it('should return the same results after clearCache', () => {
+      roleManager.registerRole(createRole({ id: 'developer', toolPermissions: { allow: ['git__commit'] } }));
+      const before = roleManager.getEffectiveToolPermissions('developer');
+
+      roleManager.clearCache();
+
+      expect(roleManager.getEffectiveToolPermissions('developer')).toEqual(before);
+    });
+  });
+
+  describe('registerRolesChecked', () => {
+    it('should flag roles overwritten by a later call', () => {
+      const first = roleManager.registerRolesChecked([
+        createRole({ id: 'developer', allowedServers: ['git'] }),
+        createRole({ id: 'guest' })
+      ]);
+      expect(first).toEqual({ added: ['developer', 'guest'], overwritten: [] });
+
+      const second = roleManager.registerRolesChecked([
+        createRole({ id: 'developer', allowedServers: ['filesystem'] }),
+        createRole({ id: 'admin' })
+      ]);
+      expect(second).toEqual({ added: ['admin'], overwritten: ['developer'] });
+
+      // Last definition wins
+      expect(roleManager.getRole('developer')?.allowedServers).toEqual(['filesystem']);
+    });
+  });
+
+  describe('exportRolesAsManifest', () => {
+    it('should round-trip role tool access through a skill manifest', async () => {
+      await roleManager.loadFromSkillManifest(createManifest([
+        createSkill({
+          id: 'reader',
+          allowedRoles: ['guest', 'developer'],
+          allowedTools: ['filesystem__read_file']
+        }),
+        createSkill({
+          id: 'committer',
+          allowedRoles: ['developer'],
+          allowedTools: ['git__commit', 'git__log'],
+          grants: { memory: 'isolated' }
+        })
+      ]));
+
+      const exported = roleManager.exportRolesAsManifest();
+      expect(exported.skills.map(s => s.id).sort()).toEqual(['role-developer', 'role-guest']);
+
+      const reloaded = new RoleManager(testLogger);
+      await reloaded.loadFromSkillManifest(exported);
+
+      const tools = ['filesystem__read_file', 'git__commit', 'git__log', 'git__push'];
+      for (const roleId of ['guest', 'developer']) {
+        for (const tool of tools) {
+          const server = RoleManager.extractServerFromTool(tool)!;
+          expect(reloaded.isToolAllowedForRole(roleId, tool, server))
+            .toBe(roleManager.isToolAllowedForRole(roleId, tool, server));
+        }
+      }
+      expect(reloaded.getMemoryPermission('developer').policy).toBe('isolated');
+    });
+  });
+
+  describe('skill tags', () => {
+    beforeEach(async () => {
+      await roleManager.loadFromSkillManifest(createManifest([
+        createSkill({
+          id: 'shell',
+          allowedRoles: ['admin', 'ops'],
+          allowedTools: ['shell__exec'],
+          metadata: { tags: ['dangerous', 'system'] }
+        }),
+        createSkill({
+          id: 'reader',
+          allowedRoles: ['guest', 'admin'],
+          allowedTools: ['filesystem__read_file'],
+          metadata: { tags: ['safe'] }
+        })
+      ]));
+    });
+
+    it('should return roles with a tagged skill', () => {
+      expect(roleManager.rolesWithSkillTag('dangerous').sort()).toEqual(['admin', 'ops']);
+      expect(roleManager.rolesWithSkillTag('safe').sort()).toEqual(['admin', 'guest']);
+      expect(roleManager.rolesWithSkillTag('unknown')).toEqual([]);
+    });
+
+    it('should return skills by tag', () => {
+      expect(roleManager.skillsByTag('dangerous').map(s => s.id)).toEqual(['shell']);
+    });
+  });
+
+  describe('defaultAllowOnServerAccess', () => {
+    const denyOnly = createRole({
+      id: 'operator',
+      allowedServers: ['shell'],
+      toolPermissions: { deny: ['shell__rm'] }
+    });
+
+    it('should treat deny-only roles as allow-list roles by default', () => {
+      roleManager.registerRole(denyOnly);
+
+      expect(roleManager.getToolAccessDecision('operator', 'shell__ls', 'shell'))
+        .toEqual({ allowed: false, reason: 'not_in_allow_list' });
+    });
+
+    it('should allow non-denied tools on allowed servers when enabled', () => {
+      const lenient = new RoleManager(testLogger, { defaultAllowOnServerAccess: true });
+      lenient.registerRole(denyOnly);
+
+      expect(lenient.getToolAccessDecision('operator', 'shell__ls', 'shell'))
+        .toEqual({ allowed: true, reason: 'server_access_granted' });
+      expect(lenient.getToolAccessDecision('operator', 'shell__rm', 'shell'))
+        .toEqual({ allowed: false, reason: 'explicitly_denied' });
+      expect(lenient.getToolAccessDecision('operator', 'git__log', 'git'))
+        .toEqual({ allowed: false, reason: 'server_not_allowed' });
+      expect(lenient.getToolAccessDecision('operator', 'shell__ls', 'shell', 'most_specific'))
+        .toEqual({ allowed: true, reason: 'server_access_granted' });
+    });
+
+    it('should keep allow lists authoritative when enabled', () => {
+      const lenient = new RoleManager(testLogger, { defaultAllowOnServerAccess: true });
+      lenient.registerRole(createRole({
+        id: 'reader',
+        allowedServers: ['shell'],
+        toolPermissions: { allow: ['shell__ls'] }
+      }));
+
+      expect(lenient.isToolAllowedForRole('reader', 'shell__cat', 'shell')).toBe(false);
+    });
+  });
+
+  describe('pattern matching', () => {
+    it('should treat regex metacharacters in patterns literally', () => {
+      roleManager.registerRole(createRole({
+        id: 'calc',
+        allowedServers: ['calc'],
+        toolPermissions: { allowPatterns: ['calc__a+b*'] }
+      }));
+
+      expect(roleManager.isToolAllowedForRole('calc', 'calc__a+b', 'calc')).toBe(true);
+      expect(roleManager.isToolAllowedForRole('calc', 'calc__aaab', 'calc')).toBe(false);
+    });
+
+    it('should honor a deny pattern across servers', () => {
+      roleManager.registerRole(createRole({
+        id: 'maintainer',
+        allowedServers: ['filesystem', 'git'],
+        toolPermissions: { allowPatterns: ['filesystem__*', 'git__*'], denyPatterns: ['*__delete_file'] }
+      }));
+
+      for (const resolution of ['deny_wins', 'most_specific'] as const) {
+        expect(roleManager.getToolAccessDecision('maintainer', 'filesystem__delete_file', 'filesystem', resolution))
+          .toEqual({ allowed: false, reason: 'denied_by_pattern' });
+        expect(roleManager.getToolAccessDecision('maintainer', 'git__delete_file', 'git', resolution))
+          .toEqual({ allowed: false, reason: 'denied_by_pattern' });
+      }
+      expect(roleManager.isToolAllowedForRole('maintainer', 'filesystem__read_file', 'filesystem')).toBe(true);
+      expect(roleManager.isToolAllowedForRole('maintainer', 'git__log', 'git')).toBe(true);
+    });
+
+    it('should apply a deny pattern to deny-only roles on every allowed server', () => {
+      const lenient = new RoleManager(testLogger, { defaultAllowOnServerAccess: true });
+      lenient.registerRole(createRole({
+        id: 'operator',
+        allowedServers: ['*'],
+        toolPermissions: { denyPatterns: ['*__delete_file'] }
+      }));
+
+      expect(lenient.isToolAllowedForRole('operator', 'filesystem__delete_file', 'filesystem')).toBe(false);
+      expect(lenient.isToolAllowedForRole('operator', 'sandbox__delete_file', 'sandbox')).toBe(false);
+      expect(lenient.isToolAllowedForRole('operator', 'sandbox__delete_files', 'sandbox')).toBe(true);
+      expect(lenient.isToolAllowedForRole('operator', 'filesystem__write_file', 'filesystem')).toBe(true);
+    });
+  });
+
+  describe('merge', () => {
+    async function createBase(): Promise<RoleManager> {
+      const base = new RoleManager(testLogger);
+      await base.loadFromSkillManifest(createManifest([
+        createSkill({ id: 'reader', allowedRoles: ['guest', 'admin'], allowedTools: ['filesystem__read_file'] })
+      ]));
+      return base;
+    }
+
+    async function createOverlay(): Promise<RoleManager> {
+      const overlay = new RoleManager(testLogger);
+      await overlay.loadFromSkillManifest(createManifest([
+        createSkill({ id: 'shell', allowedRoles: ['admin'], allowedTools: ['shell__exec'] })
+      ]));
+      return overlay;
+    }
+
+    it('should let the overlay win conflicts', async () => {
+      const base = await createBase();
+
+      const report = base.merge(await createOverlay(), 'overlay');
+
+      expect(report).toEqual({ added: [], conflicts: ['admin'] });
+      expect(base.getRoleIds().sort()).toEqual(['admin', 'guest']);
+      expect(base.isToolAllowedForRole('admin', 'shell__exec', 'shell')).toBe(true);
+      expect(base.isToolAllowedForRole('admin', 'filesystem__read_file', 'filesystem')).toBe(false);
+      expect(base.getRole('admin')?.metadata?.skills).toEqual(['shell']);
+    });
+
+    it('should keep existing roles when asked to', async () => {
+      const base = await createBase();
+
+      const report = base.merge(await createOverlay(), 'keep_existing');
+
+      expect(report.conflicts).toEqual(['admin']);
+      expect(base.isToolAllowedForRole('admin', 'filesystem__read_file', 'filesystem')).toBe(true);
+      expect(base.isToolAllowedForRole('admin', 'shell__exec', 'shell')).toBe(false);
+    });
+
+    it('should add roles that only exist in the other manager', async () => {
+      const base = await createBase();
+      const other = new RoleManager(testLogger);
+      other.registerRole(createRole({ id: 'auditor', allowedServers: ['audit'] }));
+
+      const report = base.merge(other);
+
+      expect(report).toEqual({ added: ['auditor'], conflicts: [] });
+      expect(base.hasRole('auditor')).toBe(true);
+      expect(base.hasRole('guest')).toBe(true);
+    });
+
+    it('should not share memory permissions with the other manager', async () => {
+      const base = await createBase();
+      const other = new RoleManager(testLogger);
+      other.registerRole(createRole({ id: 'lead' }));
+      other.setMemoryPermission('lead', { policy: 'team', teamRoles: ['dev'] });
+
+      base.merge(other);
+      other.setMemoryPermission('lead', { policy: 'team', teamRoles: ['qa'] });
+      base.getMemoryPermission('lead').teamRoles?.push('ops');
+
+      expect(base.getMemoryPermission('lead')).toEqual({ policy: 'team', teamRoles: ['dev'] });
+      expect(other.getMemoryPermission('lead')).toEqual({ policy: 'team', teamRoles: ['dev', 'qa'] });
+    });
+  });
+
+  describe('skill conflicts', () => {
+    const skills = [
+      createSkill({ id: 'read-only', allowedRoles: ['auditor', 'ops'], allowedTools: ['db__select'], conflicts: ['admin-write'] }),
+      createSkill({ id: 'admin-write', allowedRoles: ['ops', 'admin'], allowedTools: ['db__update'] }),
+      createSkill({ id: 'logs', allowedRoles: ['ops'], allowedTools: ['logs__tail'] })
+    ];
+
+    it('should report conflicting skills granted to one role', async () => {
+      await roleManager.loadFromSkillManifest(createManifest(skills));
+
+      expect(roleManager.conflictsForRole('ops')).toEqual([['read-only', 'admin-write']]);
+      expect(roleManager.conflictsForRole('auditor')).toEqual([]);
+      expect(roleManager.conflictsForRole('admin')).toEqual([]);
+    });
+
+    it('should reject the manifest when the policy is reject', async () => {
+      const strict = new RoleManager(testLogger, { skillConflictPolicy: 'reject' });
+      strict.registerRole(createRole({ id: 'existing' }));
+
+      await expect(strict.loadFromSkillManifest(createManifest(skills)))
+        .rejects.toThrow(SkillConflictError);
+      expect(strict.getRoleIds()).toEqual(['existing']);
+    });
+  });
+
+  describe('updateRole', () => {
+    it('should reflect in-place edits in effective servers', () => {
+      roleManager.registerRole(createRole({ id: 'base', allowedServers: ['filesystem'] }));
+      roleManager.registerRole(createRole({ id: 'child', allowedServers: [], inherits: 'base' }));
+      expect(roleManager.getEffectiveServers('child')).toEqual(['filesystem']);
+
+      const updated = roleManager.updateRole('base', role => {
+        role.allowedServers.push('git');
+      });
+
+      expect(updated).toBe(true);
+      expect(roleManager.getEffectiveServers('child').sort()).toEqual(['filesystem', 'git']);
+    });
+
+    it('should return false for an unknown role', () => {
+      expect(roleManager.updateRole('ghost', () => {})).toBe(false);
+    });
+
+    it('should not let a role returned by getRole be edited', () => {
+      roleManager.registerRole(createRole({ id: 'base', allowedServers: ['filesystem'] }));
+      expect(roleManager.getEffectiveServers('base')).toEqual(['filesystem']);
+
+      const role = roleManager.getRole('base')!;
+      expect(Object.isFrozen(role)).toBe(true);
+      expect(() => role.allowedServers.push('git')).toThrow(TypeError);
+      expect(roleManager.getEffectiveServers('base')).toEqual(['filesystem']);
+    });
+
+    it('should hand out one frozen copy until the role changes', () => {
+      roleManager.registerRole(createRole({ id: 'base', allowedServers: ['filesystem'] }));
+
+      const first = roleManager.getRole('base');
+      expect(roleManager.getRole('base')).toBe(first);
+      expect(roleManager.getAllRoles()).toContain(first);
+
+      roleManager.updateRole('base', role => {
+        role.allowedServers.push('git');
+      });
+
+      const updated = roleManager.getRole('base')!;
+      expect(updated).not.toBe(first);
+      expect(updated.allowedServers).toEqual(['filesystem', 'git']);
+      expect(first!.allowedServers).toEqual(['filesystem']);
+    });
+
+    it('should not track edits to the object passed to registerRole', () => {
+      const role = createRole({ id: 'base', allowedServers: ['filesystem'] });
+      roleManager.registerRole(role);
+
+      role.allowedServers.push('git');
+
+      expect(roleManager.getRole('base')!.allowedServers).toEqual(['filesystem']);
+      expect(roleManager.getEffectiveServers('base')).toEqual(['filesystem']);
+    });
+  });
+
+  describe('traceToolAccess', () => {
+    beforeEach(() => {
+      roleManager.registerRole(createRole({
+        id: 'base',
+        allowedServers: ['filesystem'],
+        toolPermissions: { allow: ['filesystem__read_file'], denyPatterns: ['filesystem__delete_*'] }
+      }));
+      roleManager.registerRole(createRole({
+        id: 'child',
+        allowedServers: [],
+        inherits: 'base',
+        toolPermissions: { allow: ['filesystem__write_file'] }
+      }));
+    });
+
+    it('should attribute inherited rules to the ancestor that defined them', () => {
+      expect(roleManager.traceToolAccess('child', 'filesystem__read_file', 'filesystem')).toEqual({
+        allowed: true,
+        reason: 'allowed_by_permission',
+        chain: ['child', 'base'],
+        sourceRoleId: 'base',
+        rule: 'filesystem__read_file'
+      });
+      expect(roleManager.traceToolAccess('child', 'filesystem__delete_all', 'filesystem'))
+        .toMatchObject({ allowed: false, sourceRoleId: 'base', rule: 'filesystem__delete_*' });
+    });
+
+    it('should attribute the role\'s own rules to itself', () => {
+      expect(roleManager.traceToolAccess('child', 'filesystem__write_file', 'filesystem'))
+        .toMatchObject({ allowed: true, sourceRoleId: 'child' });
+    });
+  });
+});
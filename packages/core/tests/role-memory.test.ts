@@ -0,0 +1,186 @@
+/**
+ * Unit tests for RoleMemoryStore
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import { promises as fs } from 'fs';
+import path from 'path';
+import os from 'os';
+import { RoleMemoryStore, createRoleMemoryStore } from '../src/rbac/role-memory.js';
+import { RoleManager } from '../src/rbac/role-manager.js';
+import { MemoryAccessDeniedError } from '@mycelium/shared';
+import { testLogger, createSkill, createManifest } from './helpers.js';
+
+async function createTeamRoleManager(): Promise<RoleManager> {
+  const roleManager = new RoleManager(testLogger);
+  await roleManager.loadFromSkillManifest(createManifest([
+    createSkill({ id: 'team-memory', allowedRoles: ['frontend'], grants: { memory: 'team', memoryTeamRoles: ['backend'] } }),
+    createSkill({ id: 'admin-memory', allowedRoles: ['admin'], grants: { memory: 'all' } }),
+    createSkill({ id: 'own-memory', allowedRoles: ['backend', 'guest'], grants: { memory: 'isolated' } })
+  ]));
+  return roleManager;
+}
+
+describe('RoleMemoryStore', () => {
+  let store: RoleMemoryStore;
+  let testDir: string;
+
+  beforeEach(async () => {
+    testDir = path.join(os.tmpdir(), `role-memory-test-${Date.now()}`);
+    store = createRoleMemoryStore(testDir, await createTeamRoleManager(), testLogger);
+    await store.initialize();
+  });
+
+  afterEach(async () => {
+    try {
+      await fs.rm(testDir, { recursive: true });
+    } catch {
+      // Ignore cleanup errors
+    }
+  });
+
+  describe('keys', () => {
+    it('should list entry IDs for the owning role', async () => {
+      const first = await store.writeAs('admin', 'backend', 'Uses PostgreSQL');
+      const second = await store.writeAs('admin', 'backend', 'Prefers tabs', { type: 'preference' });
+      await store.writeAs('admin', 'frontend', 'Uses React');
+
+      const keys = await store.keys('backend');
+      expect(keys).toHaveLength(2);
+      expect(keys).toEqual(expect.arrayContaining([first.id, second.id]));
+    });
+
+    it('should return empty list for a role without memory', async () => {
+      expect(await store.keys('nobody')).toEqual([]);
+    });
+  });
+
+  describe('entriesFor', () => {
+    it('should let a team role enumerate a teammate\'s entries', async () => {
+      const entry = await store.writeAs('admin', 'backend', 'API lives under /v2');
+
+      const entries = await store.entriesFor('frontend', 'backend');
+      expect(entries.map(e => e.id)).toEqual([entry.id]);
+    });
+
+    it('should reject enumeration outside the accessor\'s policy', async () => {
+      await store.writeAs('admin', 'frontend', 'Design tokens in theme.ts');
+
+      await expect(store.entriesFor('guest', 'frontend'))
+        .rejects.toThrow(/cannot access memory/);
+    });
+  });
+
+  describe('clear', () => {
+    it('should empty only the target role', async () => {
+      await store.writeAs('admin', 'backend', 'Uses PostgreSQL');
+      await store.writeAs('admin', 'frontend', 'Uses React');
+
+      await store.clear('backend');
+
+      expect(await store.keys('backend')).toEqual([]);
+      expect(await store.keys('frontend')).toHaveLength(1);
+    });
+
+    it('should not touch roles whose ids differ only by punctuation', async () => {
+      await store.writeAs('admin', 'team.a', 'Dotted role');
+      await store.writeAs('admin', 'team_a', 'Underscored role');
+
+      await store.clear('team.a');
+
+      // Fresh store so nothing is served from the cache
+      const reopened = createRoleMemoryStore(testDir, await createTeamRoleManager(), testLogger);
+      expect(await reopened.keys('team.a')).toEqual([]);
+      expect(await reopened.keys('team_a')).toHaveLength(1);
+      expect((await reopened.listRolesWithMemory()).sort()).toEqual(['team.a', 'team_a']);
+    });
+  });
+
+  describe('legacy file names', () => {
+    // 'team.a' used to be stored as team_a.memory.md, the same file as role 'team_a'
+    async function writeLegacyTeamAFile(content: string): Promise<void> {
+      const scratchDir = `${testDir}-scratch`;
+      const scratch = createRoleMemoryStore(scratchDir, await createTeamRoleManager(), testLogger);
+      await scratch.initialize();
+      await scratch.writeAs('admin', 'team.a', content);
+      await fs.rename(path.join(scratchDir, 'team%2Ea.memory.md'), path.join(testDir, 'team_a.memory.md'));
+      await fs.rm(scratchDir, { recursive: true });
+    }
+
+    async function reopen(): Promise<RoleMemoryStore> {
+      const reopened = createRoleMemoryStore(testDir, await createTeamRoleManager(), testLogger);
+      await reopened.initialize();
+      return reopened;
+    }
+
+    it('should migrate a legacy file to its owner and not serve it to a look-alike role', async () => {
+      await writeLegacyTeamAFile('Dotted role memory');
+
+      const reopened = await reopen();
+
+      expect((await reopened.readAs('admin', 'team.a')).map(e => e.content)).toEqual(['Dotted role memory']);
+      expect(await reopened.keys('team_a')).toEqual([]);
+      expect(await reopened.listRolesWithMemory()).toEqual(['team.a']);
+    });
+
+    it('should refuse a legacy file whose encoded name is already taken', async () => {
+      await store.writeAs('admin', 'team.a', 'Current memory');
+      await writeLegacyTeamAFile('Legacy memory');
+
+      const reopened = await reopen();
+
+      await expect(reopened.keys('team_a')).rejects.toThrow(/belongs to role 'team.a'/);
+      expect((await reopened.readAs('admin', 'team.a')).map(e => e.content)).toEqual(['Current memory']);
+    });
+
+    it('should skip files with undecodable names when listing roles', async () => {
+      await store.writeAs('admin', 'backend', 'Uses PostgreSQL');
+      await fs.writeFile(path.join(testDir, 'bad%zz.memory.md'), '', 'utf-8');
+
+      expect(await store.listRolesWithMemory()).toEqual(['backend']);
+    });
+  });
+
+  describe('readAs / writeAs', () => {
+    it('should refuse a guest reading admin memory', async () => {
+      await store.writeAs('admin', 'admin', 'Root password rotates monthly');
+
+      await expect(store.readAs('guest', 'admin')).rejects.toThrow(MemoryAccessDeniedError);
+    });
+
+    it('should let an all-policy role read any memory', async () => {
+      await store.writeAs('admin', 'guest', 'Prefers dark mode');
+      await store.writeAs('admin', 'backend', 'Uses PostgreSQL');
+
+      expect((await store.readAs('admin', 'guest')).map(e => e.content)).toEqual(['Prefers dark mode']);
+      expect((await store.readAs('admin', 'backend')).map(e => e.content)).toEqual(['Uses PostgreSQL']);
+    });
+
+    it('should only allow writes to the accessor\'s own memory', async () => {
+      await store.writeAs('backend', 'backend', 'Uses PostgreSQL');
+
+      await expect(store.writeAs('frontend', 'backend', 'Overwrite'))
+        .rejects.toThrow(/cannot write to memory of role 'backend'/);
+      await expect(store.writeAs('developer', 'developer', 'No memory grant'))
+        .rejects.toThrow(MemoryAccessDeniedError);
+
+      await store.writeAs('admin', 'backend', 'Reviewed by admin');
+      expect(await store.keys('backend')).toHaveLength(2);
+    });
+
+    it('should only let an all-policy role search across roles', async () => {
+      await store.writeAs('admin', 'backend', 'Uses PostgreSQL');
+      await store.writeAs('admin', 'frontend', 'Uses React');
+
+      expect(await store.searchAllAs('admin')).toHaveLength(2);
+      await expect(store.searchAllAs('frontend')).rejects.toThrow(MemoryAccessDeniedError);
+    });
+
+    it('should only let a role recall its own memory when it has a memory grant', async () => {
+      await store.writeAs('backend', 'backend', 'Uses PostgreSQL');
+
+      expect((await store.recall('backend', 'postgres')).map(e => e.content)).toEqual(['Uses PostgreSQL']);
+      await expect(store.recall('developer', 'postgres')).rejects.toThrow(MemoryAccessDeniedError);
+    });
+  });
+});
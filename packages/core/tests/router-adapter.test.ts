@@ -111,7 +111,7 @@ describe('RouterAdapter', () => {
     });
 
     it('should start and stop servers', async () => {
-      await expect(adapter.startServers()).resolves.toBeUndefined();
+      await expect(adapter.startServers()).resolves.toEqual({ started: [], failed: [] });
       await expect(adapter.stopServers()).resolves.toBeUndefined();
     });
   });
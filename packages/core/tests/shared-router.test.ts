@@ -0,0 +1,104 @@
+/**
+ * Unit tests for router/shared-router.ts
+ */
+
+import { describe, it, expect, vi, beforeEach } from 'vitest';
+import { MyceliumCore } from '../src/router/mycelium-core.js';
+import { SharedRouter, createSharedRouter } from '../src/router/shared-router.js';
+import { ToolNotAccessibleError } from '@mycelium/shared';
+import { testLogger, createSkill } from './helpers.js';
+
+const skills = [
+  createSkill({ id: 'reader', allowedRoles: ['guest', 'admin'], allowedTools: ['alpha__read'] }),
+  createSkill({ id: 'writer', allowedRoles: ['admin'], allowedTools: ['alpha__write'] })
+];
+
+const catalog = [
+  { name: 'alpha__read', inputSchema: { type: 'object', properties: {} } },
+  { name: 'alpha__write', inputSchema: { type: 'object', properties: {} } }
+];
+
+async function createLoadedCore(): Promise<MyceliumCore> {
+  const core = new MyceliumCore(testLogger);
+  const stdio = core.getStdioRouter();
+  vi.spyOn(stdio, 'routeRequest').mockImplementation(async (request: any) => {
+    if (request.method === 'tools/list') {
+      return { result: { tools: catalog } };
+    }
+    return { result: { content: [{ type: 'text', text: JSON.stringify({ skills }) }] } };
+  });
+  vi.spyOn(stdio, 'startServersByName').mockResolvedValue({ started: ['alpha'], failed: [] });
+  vi.spyOn(stdio, 'getAvailableServers').mockReturnValue([{ name: 'alpha', connected: true }]);
+  vi.spyOn(core, 'exportSessionState').mockResolvedValue();
+
+  await core.loadRolesFromSkillsServer();
+  await core.startServersForRole('admin');
+  return core;
+}
+
+describe('SharedRouter', () => {
+  let core: MyceliumCore;
+  let shared: SharedRouter;
+
+  beforeEach(async () => {
+    core = await createLoadedCore();
+    shared = createSharedRouter(core);
+  });
+
+  it('should switch roles and report visible tools', async () => {
+    await shared.setRole({ role: 'guest' });
+    expect(await shared.getVisibleTools()).toContain('alpha__read');
+    expect(await shared.getVisibleTools()).not.toContain('alpha__write');
+
+    await shared.setRole({ role: 'admin' });
+    expect(await shared.getVisibleTools()).toContain('alpha__write');
+  });
+
+  it('should check tool access against the active role', async () => {
+    await shared.setRole({ role: 'guest' });
+    await expect(shared.checkToolAccess('alpha__read')).resolves.toBeUndefined();
+    await expect(shared.checkToolAccess('alpha__write')).rejects.toThrow(ToolNotAccessibleError);
+  });
+
+  it('should give concurrent sequences a consistent view', async () => {
+    const switchAndRead = (role: string) =>
+      shared.run(async router => {
+        await router.setRole({ role });
+        // Yield so an unserialized sequence could interleave here
+        await new Promise(resolve => setTimeout(resolve, 5));
+        return {
+          role: router.getCurrentRole()?.id,
+          tools: router.getContext().availableTools.map(tool => tool.name)
+        };
+      });
+
+    const [guest, admin] = await Promise.all([switchAndRead('guest'), switchAndRead('admin')]);
+
+    expect(guest.role).toBe('guest');
+    expect(guest.tools).not.toContain('alpha__write');
+    expect(admin.role).toBe('admin');
+    expect(admin.tools).toContain('alpha__write');
+    expect(core.getCurrentRole()?.id).toBe('admin');
+  });
+
+  it('should run operations in submission order', async () => {
+    const order: string[] = [];
+    await Promise.all([
+      shared.run(async () => {
+        await new Promise(resolve => setTimeout(resolve, 10));
+        order.push('slow');
+      }),
+      shared.run(() => {
+        order.push('fast');
+      })
+    ]);
+
+    expect(order).toEqual(['slow', 'fast']);
+  });
+
+  it('should keep the queue usable after a failed operation', async () => {
+    await expect(shared.setRole({ role: 'nonexistent' })).rejects.toThrow();
+    await shared.setRole({ role: 'guest' });
+    expect(core.getCurrentRole()?.id).toBe('guest');
+  });
+});
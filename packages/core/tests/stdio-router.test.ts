@@ -4,7 +4,12 @@
 
 import { describe, it, expect, vi, beforeEach, afterEach } from 'vitest';
 import { StdioRouter, type UpstreamServerInfo } from '../src/mcp/stdio-router.js';
-import type { Logger } from '@mycelium/shared';
+import {
+  ServerNotStartedError,
+  MalformedResponseError,
+  ToolCallFailedError,
+  type Logger
+} from '@mycelium/shared';
 import { EventEmitter } from 'events';
 
 // Mock child_process
@@ -162,6 +167,23 @@ describe('StdioRouter', () => {
       // Should log error
       expect(logger.error).toHaveBeenCalled();
     });
+
+    it('should kill the process when initialization times out', async () => {
+      vi.useFakeTimers();
+      try {
+        const report = router.startServers();
+        await vi.advanceTimersByTimeAsync(30000);
+
+        expect((await report).failed).toEqual([
+          { name: 'test-server', error: 'Request timeout for test-server' }
+        ]);
+        expect(mockProcess.kill).toHaveBeenCalled();
+        expect(router.getAvailableServers()).toEqual([{ name: 'test-server', connected: false }]);
+        expect(router.getServerStates()).toEqual({ 'test-server': 'crashed' });
+      } finally {
+        vi.useRealTimers();
+      }
+    });
   });
 
   describe('startServersByName', () => {
@@ -200,6 +222,122 @@ describe('StdioRouter', () => {
     });
   });
 
+  describe('getServerStates', () => {
+    it('should report not-started, starting, running and crashed servers', async () => {
+      router.addServerFromConfig('idle', { command: 'node', args: [] });
+      router.addServerFromConfig('echo', { command: 'node', args: ['echo.js'] });
+      expect(router.getServerStates()).toEqual({ idle: 'not_started', echo: 'not_started' });
+
+      // Complete the MCP handshake
+      mockStdin.write = vi.fn((line: string) => {
+        const request = JSON.parse(line);
+        if (request.id !== undefined) {
+          const response = { jsonrpc: '2.0', id: request.id, result: { capabilities: {} } };
+          setImmediate(() => mockStdout.emit('data', Buffer.from(JSON.stringify(response) + '\n')));
+        }
+        return true;
+      });
+      const started = router.startServersByName(['echo']);
+      expect(router.getServerStates()).toEqual({ idle: 'not_started', echo: 'starting' });
+      await started;
+      expect(router.getServerStates()).toEqual({ idle: 'not_started', echo: 'running' });
+
+      mockProcess.emit('close', 1);
+      expect(router.getServerStates()).toEqual({ idle: 'not_started', echo: 'crashed' });
+    });
+  });
+
+  describe('callTool', () => {
+    let requests: any[];
+
+    /**
+     * Answer each JSON-RPC request written to stdin like a minimal MCP server
+     */
+    function respondWith(handler: (request: any) => any): void {
+      mockStdin.write = vi.fn((line: string) => {
+        const request = JSON.parse(line);
+        if (request.id === undefined) return true; // notification
+        requests.push(request);
+        const response = request.method === 'initialize'
+          ? { jsonrpc: '2.0', id: request.id, result: { capabilities: {} } }
+          : { jsonrpc: '2.0', id: request.id, ...handler(request) };
+        setImmediate(() => mockStdout.emit('data', Buffer.from(JSON.stringify(response) + '\n')));
+        return true;
+      });
+    }
+
+    beforeEach(() => {
+      requests = [];
+      router.addServerFromConfig('echo', { command: 'node', args: ['echo.js'] });
+    });
+
+    it('should frame a tools/call request and return the result', async () => {
+      respondWith((request) => ({
+        result: { content: [{ type: 'text', text: `${request.params.name}:${request.params.arguments.path}` }] }
+      }));
+      await router.startServersByName(['echo']);
+
+      const result = await router.callTool('echo', 'read_file', { path: '/tmp/a.txt' });
+
+      expect(result.content).toEqual([{ type: 'text', text: 'read_file:/tmp/a.txt' }]);
+      const call = requests.find(r => r.method === 'tools/call');
+      expect(call).toMatchObject({
+        jsonrpc: '2.0',
+        method: 'tools/call',
+        params: { name: 'read_file', arguments: { path: '/tmp/a.txt' } }
+      });
+    });
+
+    it('should reject calls to a server that is not started', async () => {
+      await expect(router.callTool('echo', 'read_file')).rejects.toThrow(ServerNotStartedError);
+      await expect(router.callTool('missing', 'read_file')).rejects.toThrow(ServerNotStartedError);
+    });
+
+    it('should reject malformed responses', async () => {
+      respondWith(() => ({ result: 'not a tool result' }));
+      await router.startServersByName(['echo']);
+
+      await expect(router.callTool('echo', 'read_file')).rejects.toThrow(MalformedResponseError);
+    });
+
+    it('should surface JSON-RPC errors as ToolCallFailedError', async () => {
+      respondWith(() => ({ error: { code: -32602, message: 'Invalid params' } }));
+      await router.startServersByName(['echo']);
+
+      const error = await router.callTool('echo', 'read_file').catch(e => e);
+      expect(error).toBeInstanceOf(ToolCallFailedError);
+      expect(error.code).toBe(-32602);
+      expect(error.message).toContain('Invalid params');
+      expect(error.cause).toEqual({ code: -32602, message: 'Invalid params' });
+    });
+
+    it('should surface a timed-out call as ToolCallFailedError', async () => {
+      // Answer the handshake but never the tool call
+      respondWith(() => null);
+      const answer = mockStdin.write;
+      mockStdin.write = vi.fn((line: string) => {
+        if (JSON.parse(line).method === 'tools/call') return true;
+        return answer(line);
+      });
+      await router.startServersByName(['echo']);
+
+      vi.useFakeTimers();
+      try {
+        const call = router.callTool('echo', 'read_file').catch(e => e);
+        await vi.advanceTimersByTimeAsync(30000);
+
+        const error = await call;
+        expect(error).toBeInstanceOf(ToolCallFailedError);
+        expect(error.serverName).toBe('echo');
+        expect(error.toolName).toBe('read_file');
+        expect(error.message).toContain('Request timeout for echo');
+        expect(error.cause).toBeInstanceOf(Error);
+      } finally {
+        vi.useRealTimers();
+      }
+    });
+  });
+
   describe('event handling', () => {
     it('should emit notification events', () => {
       const handler = vi.fn();
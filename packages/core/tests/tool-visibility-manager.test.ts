@@ -0,0 +1,522 @@
+/**
+ * Unit tests for ToolVisibilityManager
+ */
+
+import { describe, it, expect, beforeEach, afterEach, vi } from 'vitest';
+import { ToolVisibilityManager } from '../src/rbac/tool-visibility-manager.js';
+import { RoleManager } from '../src/rbac/role-manager.js';
+import { SystemTools, ToolNotAccessibleError } from '@mycelium/shared';
+import type { BaseSkillDefinition, Role } from '@mycelium/shared';
+import type { Tool } from '@modelcontextprotocol/sdk/types.js';
+import { testLogger, createManifest, createTool } from './helpers.js';
+
+async function createRoleManager(skills: BaseSkillDefinition[]): Promise<RoleManager> {
+  const roleManager = new RoleManager(testLogger);
+  await roleManager.loadFromSkillManifest(createManifest(skills));
+  return roleManager;
+}
+
+describe('ToolVisibilityManager', () => {
+  let roleManager: RoleManager;
+  let manager: ToolVisibilityManager;
+
+  beforeEach(async () => {
+    roleManager = await createRoleManager([
+      {
+        id: 'data-access',
+        displayName: 'Data Access',
+        description: 'Read files and query the database',
+        allowedRoles: ['developer'],
+        allowedTools: ['alpha__read', 'beta__query']
+      }
+    ]);
+    manager = new ToolVisibilityManager(testLogger, roleManager);
+  });
+
+  describe('server availability', () => {
+    beforeEach(() => {
+      manager.registerToolsFromList([createTool('alpha__read'), createTool('beta__query')]);
+    });
+
+    it('should hide tools of an unavailable server with a distinct reason', () => {
+      manager.setServerAvailability('beta', false);
+      manager.setCurrentRole(roleManager.getRole('developer')!);
+
+      expect(manager.isVisible('alpha__read')).toBe(true);
+      expect(manager.isVisible('beta__query')).toBe(false);
+      expect(manager.getToolInfo('beta__query')?.visibilityReason).toBe('backend_unavailable');
+    });
+
+    it('should keep unavailable tools registered across rediscovery', () => {
+      manager.setServerAvailability('beta', false);
+      manager.registerToolsFromList([createTool('alpha__read')]);
+      manager.setCurrentRole(roleManager.getRole('developer')!);
+
+      expect(manager.getTotalCount()).toBe(2);
+      expect(manager.getToolInfo('beta__query')?.visibilityReason).toBe('backend_unavailable');
+    });
+
+    it('should show tools again once the server is available', () => {
+      manager.setServerAvailability('beta', false);
+      manager.setCurrentRole(roleManager.getRole('developer')!);
+      manager.setServerAvailability('beta', true);
+      manager.setCurrentRole(roleManager.getRole('developer')!);
+
+      expect(manager.isVisible('beta__query')).toBe(true);
+    });
+  });
+
+  describe('visibility reasons', () => {
+    const restricted: Role = {
+      id: 'restricted',
+      name: 'Restricted',
+      description: 'Role with explicit tool permissions',
+      allowedServers: ['alpha'],
+      systemInstruction: '',
+      toolPermissions: {
+        allow: ['alpha__read'],
+        deny: ['alpha__delete'],
+        denyPatterns: ['alpha__drop_*']
+      }
+    };
+
+    beforeEach(() => {
+      roleManager.registerRole(restricted);
+      manager.registerToolsFromList([
+        createTool('alpha__read'),
+        createTool('alpha__write'),
+        createTool('alpha__delete'),
+        createTool('alpha__drop_table'),
+        createTool('beta__query')
+      ]);
+    });
+
+    it('should report no_role when no role is set', () => {
+      manager.setCurrentRole(null);
+
+      expect(manager.getToolInfo('beta__query')?.visibilityReason).toBe('no_role');
+    });
+
+    it('should report the rule that decided each tool', () => {
+      manager.setCurrentRole(restricted);
+
+      expect(manager.getToolInfo('alpha__read')?.visibilityReason).toBe('allowed_by_permission');
+      expect(manager.getToolInfo('alpha__write')?.visibilityReason).toBe('not_in_allow_list');
+      expect(manager.getToolInfo('alpha__delete')?.visibilityReason).toBe('explicitly_denied');
+      expect(manager.getToolInfo('alpha__drop_table')?.visibilityReason).toBe('denied_by_pattern');
+      expect(manager.getToolInfo('beta__query')?.visibilityReason).toBe('server_not_allowed');
+    });
+
+    it('should report server_access_granted for roles without tool permissions', () => {
+      roleManager.registerRole({
+        id: 'open',
+        name: 'Open',
+        description: 'Server-level access only',
+        allowedServers: ['beta'],
+        systemInstruction: ''
+      });
+      manager.setCurrentRole(roleManager.getRole('open')!);
+
+      expect(manager.getToolInfo('beta__query')?.visibilityReason).toBe('server_access_granted');
+    });
+
+    it('should report skill_restricted when no active skill allows the tool', () => {
+      manager.loadSkillDefinitions([{
+        id: 'reader',
+        displayName: 'Reader',
+        description: 'Read only',
+        allowedRoles: ['restricted'],
+        allowedTools: ['alpha__read']
+      }]);
+      manager.setCurrentRole(roleManager.getRole('developer')!);
+      manager.setActiveSkills(['reader']);
+
+      expect(manager.getToolInfo('alpha__write')?.visibilityReason).toBe('skill_restricted');
+    });
+
+    it('should attach the reason to access errors', () => {
+      manager.setCurrentRole(restricted);
+
+      try {
+        manager.checkAccess('alpha__delete');
+        expect.fail('checkAccess should throw');
+      } catch (error) {
+        expect(error).toBeInstanceOf(ToolNotAccessibleError);
+        expect((error as ToolNotAccessibleError).visibilityReason).toBe('explicitly_denied');
+      }
+    });
+  });
+
+  describe('diffRoles', () => {
+    const admin: Role = {
+      id: 'admin',
+      name: 'Admin',
+      description: 'Full access',
+      allowedServers: ['*'],
+      systemInstruction: ''
+    };
+    const guest: Role = {
+      id: 'guest',
+      name: 'Guest',
+      description: 'Read-only access',
+      allowedServers: ['alpha', 'gamma'],
+      systemInstruction: '',
+      toolPermissions: { allowPatterns: ['*__read', 'gamma__*'] }
+    };
+
+    beforeEach(() => {
+      roleManager.registerRole(admin);
+      roleManager.registerRole(guest);
+      manager.registerToolsFromList([
+        createTool('alpha__read'),
+        createTool('alpha__write'),
+        createTool('beta__query')
+      ]);
+      manager.setCurrentRole(roleManager.getRole('developer')!);
+    });
+
+    it('should split tools by which role can see them', () => {
+      expect(manager.diffRoles(admin, guest)).toEqual({
+        onlyA: ['alpha__write', 'beta__query'],
+        onlyB: [],
+        both: ['alpha__read']
+      });
+    });
+
+    it('should not change the current role', () => {
+      manager.diffRoles(admin, guest);
+
+      expect(manager.isVisible('beta__query')).toBe(true);
+      expect(manager.isVisible('alpha__write')).toBe(false);
+    });
+  });
+
+  describe('snapshotVisibility', () => {
+    const guest: Role = {
+      id: 'guest',
+      name: 'Guest',
+      description: 'Read-only access',
+      allowedServers: ['alpha', 'gamma'],
+      systemInstruction: '',
+      toolPermissions: { allowPatterns: ['*__read', 'gamma__*'] }
+    };
+
+    beforeEach(() => {
+      roleManager.registerRole(guest);
+      manager.registerToolsFromList([
+        createTool('gamma__list'),
+        createTool('beta__query'),
+        createTool('alpha__write'),
+        createTool('alpha__read')
+      ]);
+      manager.setCurrentRole(guest);
+    });
+
+    it('should list every tool with its verdict and reason, sorted by name', () => {
+      expect(manager.snapshotVisibility()).toEqual([
+        { name: 'alpha__read', visible: true, reason: 'allowed_by_permission' },
+        { name: 'alpha__write', visible: false, reason: 'not_in_allow_list' },
+        { name: 'beta__query', visible: false, reason: 'server_not_allowed' },
+        { name: 'gamma__list', visible: true, reason: 'allowed_by_permission' }
+      ]);
+    });
+
+    it('should not change visible tools', () => {
+      const before = manager.getVisibleTools().map(t => t.name);
+
+      manager.snapshotVisibility();
+
+      expect(manager.getVisibleTools().map(t => t.name)).toEqual(before);
+      expect(manager.snapshotVisibility()).toEqual(manager.snapshotVisibility());
+    });
+  });
+
+  describe('tool aliases', () => {
+    const reader: Role = {
+      id: 'reader',
+      name: 'Reader',
+      description: 'Read through the fs server',
+      allowedServers: ['fs'],
+      systemInstruction: '',
+      toolPermissions: { allow: ['fs__read'] }
+    };
+
+    beforeEach(() => {
+      roleManager.registerRole(reader);
+      manager.registerToolsFromList([
+        createTool('filesystem__read_file'),
+        createTool('filesystem__write_file')
+      ]);
+      manager.setCurrentRole(reader);
+    });
+
+    it('should make an aliased tool visible when the role allows its canonical name', () => {
+      expect(manager.isVisible('filesystem__read_file')).toBe(false);
+
+      manager.addAlias('fs__read', 'filesystem__read_file');
+
+      expect(manager.isVisible('filesystem__read_file')).toBe(true);
+      expect(manager.isVisible('filesystem__write_file')).toBe(false);
+      expect(manager.resolveAlias('filesystem__read_file')).toBe('fs__read');
+    });
+
+    it('should apply a deny written against the alias', () => {
+      roleManager.registerRole({
+        ...reader,
+        toolPermissions: { allow: ['fs__read'], deny: ['filesystem__read_file'] }
+      });
+      manager.setCurrentRole(roleManager.getRole('reader')!);
+      manager.addAlias('fs__read', 'filesystem__read_file');
+
+      expect(manager.isVisible('filesystem__read_file')).toBe(false);
+      expect(() => manager.checkAccess('filesystem__read_file')).toThrow(ToolNotAccessibleError);
+    });
+
+    it('should apply a deny pattern matching only the alias', () => {
+      roleManager.registerRole({
+        ...reader,
+        toolPermissions: { allow: ['fs__read'], denyPatterns: ['filesystem__*'] }
+      });
+      manager.setCurrentRole(roleManager.getRole('reader')!);
+      manager.addAlias('fs__read', 'filesystem__read_file');
+
+      expect(manager.isVisible('filesystem__read_file')).toBe(false);
+    });
+
+    it('should reject an alias that refers to itself', () => {
+      manager.addAlias('fs__read', 'filesystem__read_file');
+
+      expect(() => manager.addAlias('filesystem__read_file', 'fs__read')).toThrow('would refer to itself');
+    });
+  });
+
+  describe('pattern resolution', () => {
+    const reader: Role = {
+      id: 'reader',
+      name: 'Reader',
+      description: 'Reads files but nothing else on fs',
+      allowedServers: ['fs'],
+      systemInstruction: '',
+      toolPermissions: {
+        allowPatterns: ['fs__read_*'],
+        denyPatterns: ['fs__*']
+      }
+    };
+
+    beforeEach(() => {
+      roleManager.registerRole(reader);
+    });
+
+    it('should let deny win by default', () => {
+      manager.registerToolsFromList([createTool('fs__read_file')]);
+      manager.setCurrentRole(reader);
+
+      expect(manager.isVisible('fs__read_file')).toBe(false);
+      expect(manager.getToolInfo('fs__read_file')?.visibilityReason).toBe('denied_by_pattern');
+    });
+
+    it('should apply the most specific pattern in most_specific mode', () => {
+      const specific = new ToolVisibilityManager(testLogger, roleManager, { patternResolution: 'most_specific' });
+      specific.registerToolsFromList([createTool('fs__read_file'), createTool('fs__write_file')]);
+      specific.setCurrentRole(reader);
+
+      expect(specific.isVisible('fs__read_file')).toBe(true);
+      expect(specific.isVisible('fs__write_file')).toBe(false);
+    });
+
+    it('should let deny win ties in most_specific mode', () => {
+      roleManager.registerRole({
+        ...reader,
+        id: 'tied',
+        toolPermissions: { allowPatterns: ['fs__*_file'], denyPatterns: ['fs__read_*'] }
+      });
+      const specific = new ToolVisibilityManager(testLogger, roleManager, { patternResolution: 'most_specific' });
+      specific.registerToolsFromList([createTool('fs__read_file')]);
+      specific.setCurrentRole(roleManager.getRole('tied')!);
+
+      expect(specific.isVisible('fs__read_file')).toBe(false);
+    });
+  });
+
+  describe('registerToolsChecked', () => {
+    it('should report a duplicate prefixed name and keep the last registration', () => {
+      const first = { ...createTool('alpha__read'), description: 'first' };
+      const second = { ...createTool('alpha__read'), description: 'second' };
+
+      const report = manager.registerToolsChecked([first, createTool('alpha__write'), second], 'alpha');
+
+      expect(report).toEqual({ added: ['alpha__read', 'alpha__write'], overwritten: ['alpha__read'] });
+      expect(manager.getTotalCount()).toBe(2);
+      expect(manager.getToolInfo('alpha__read')?.tool.description).toBe('second');
+    });
+  });
+
+  describe('replaceServerTools', () => {
+    it('should drop tools the server no longer lists and register new ones', () => {
+      manager.registerTools([createTool('alpha__read'), createTool('alpha__old')], 'alpha');
+      manager.registerTools([createTool('beta__query')], 'beta');
+
+      const report = manager.replaceServerTools('alpha', [createTool('alpha__read'), createTool('alpha__write')]);
+
+      expect(report).toEqual({ added: ['alpha__write'], removed: ['alpha__old'] });
+      expect(manager.getToolInfo('alpha__old')).toBeUndefined();
+      expect(manager.getToolInfo('alpha__write')?.sourceServer).toBe('alpha');
+      expect(manager.getToolInfo('beta__query')).toBeDefined();
+    });
+
+    it('should hide a removed tool that was visible', () => {
+      manager.registerTools([createTool('alpha__read')], 'alpha');
+      manager.setCurrentRole(roleManager.getRole('developer')!);
+      expect(manager.isVisible('alpha__read')).toBe(true);
+
+      manager.replaceServerTools('alpha', []);
+
+      expect(manager.isVisible('alpha__read')).toBe(false);
+    });
+  });
+
+  describe('pruneOlderThan', () => {
+    afterEach(() => {
+      vi.useRealTimers();
+    });
+
+    it('should remove only tools registered before the cutoff', () => {
+      vi.useFakeTimers();
+      vi.setSystemTime(new Date('2024-01-01T00:00:00Z'));
+      manager.registerTools([createTool('alpha__read')], 'alpha');
+
+      vi.setSystemTime(new Date('2024-01-01T01:00:00Z'));
+      manager.registerTools([createTool('beta__query')], 'beta');
+
+      const removed = manager.pruneOlderThan(new Date('2024-01-01T00:30:00Z'));
+
+      expect(removed).toEqual(['alpha__read']);
+      expect(manager.getToolInfo('alpha__read')).toBeUndefined();
+      expect(manager.getToolInfo('beta__query')?.registeredAt).toEqual(new Date('2024-01-01T01:00:00Z'));
+    });
+  });
+
+  describe('deny pattern parity with RoleManager', () => {
+    it('should hide tools matching a cross-server deny pattern', () => {
+      roleManager.registerRole({
+        id: 'maintainer',
+        name: 'Maintainer',
+        description: 'Maintains files and history',
+        allowedServers: ['filesystem', 'git'],
+        systemInstruction: '',
+        toolPermissions: { allowPatterns: ['filesystem__*', 'git__*'], denyPatterns: ['*__delete_file'] }
+      });
+      manager.registerToolsFromList([
+        createTool('filesystem__read_file'),
+        createTool('filesystem__delete_file'),
+        createTool('git__log'),
+        createTool('git__delete_file')
+      ]);
+
+      manager.setCurrentRole(roleManager.getRole('maintainer')!);
+
+      for (const name of ['filesystem__read_file', 'filesystem__delete_file', 'git__log', 'git__delete_file']) {
+        const { serverName } = manager.parseToolName(name);
+        expect(manager.isVisible(name)).toBe(roleManager.isToolAllowedForRole('maintainer', name, serverName));
+      }
+      expect(manager.getToolInfo('git__delete_file')?.visibilityReason).toBe('denied_by_pattern');
+      expect(manager.isVisible('git__log')).toBe(true);
+    });
+  });
+
+  describe('system tools', () => {
+    it('should keep default router tools visible without any role', () => {
+      manager.registerTools([createTool('mycelium-router__list_roles')], 'mycelium-router');
+      manager.setCurrentRole(null);
+
+      expect(manager.isVisible('mycelium-router__list_roles')).toBe(true);
+      expect(manager.getToolInfo('mycelium-router__list_roles')?.visibilityReason).toBe('system_tool');
+    });
+
+    it('should not treat a backend tool with a system-tool suffix as a system tool', () => {
+      roleManager.registerRole({
+        id: 'guest',
+        name: 'Guest',
+        description: 'No tool access',
+        allowedServers: [],
+        systemInstruction: ''
+      });
+      manager.registerTools([createTool('evil__set_role')], 'evil');
+
+      manager.setCurrentRole(roleManager.getRole('guest')!);
+
+      expect(manager.isVisible('evil__set_role')).toBe(false);
+      expect(() => manager.checkAccess('evil__set_role')).toThrow(ToolNotAccessibleError);
+    });
+
+    it('should always show a registered custom system tool regardless of role', () => {
+      const systemTools = new SystemTools();
+      systemTools.register('ops__health');
+      manager = new ToolVisibilityManager(testLogger, roleManager, { systemTools });
+      manager.registerTools([createTool('ops__health'), createTool('ops__restart')], 'ops');
+
+      manager.setCurrentRole(roleManager.getRole('developer')!);
+
+      expect(manager.isVisible('ops__health')).toBe(true);
+      expect(() => manager.checkAccess('ops__health')).not.toThrow();
+      expect(manager.isVisible('ops__restart')).toBe(false);
+      expect(manager.getSystemTools()).toBe(systemTools);
+    });
+  });
+
+  describe('input schema passthrough', () => {
+    const schema: Tool['inputSchema'] = {
+      type: 'object',
+      properties: {
+        path: { type: 'string', description: 'File path' },
+        options: {
+          type: 'object',
+          properties: { encoding: { type: 'string', enum: ['utf-8', 'base64'] } }
+        }
+      },
+      required: ['path']
+    };
+
+    it('should keep the schema intact across hide and show', () => {
+      manager.registerToolsFromList([{ name: 'alpha__read', description: 'Read', inputSchema: schema }]);
+      const guest: Role = {
+        id: 'nobody',
+        name: 'Nobody',
+        description: 'No servers',
+        allowedServers: [],
+        systemInstruction: ''
+      };
+      roleManager.registerRole(guest);
+
+      manager.setCurrentRole(guest);
+      expect(manager.getVisibleTools().map(t => t.name)).not.toContain('alpha__read');
+
+      manager.setCurrentRole(roleManager.getRole('developer')!);
+      const visible = manager.getVisibleTools().find(t => t.name === 'alpha__read');
+      expect(visible?.inputSchema).toEqual(schema);
+    });
+  });
+
+  describe('default allow on server access', () => {
+    it('should match RoleManager decisions for deny-only roles', () => {
+      const lenient = new RoleManager(testLogger, { defaultAllowOnServerAccess: true });
+      const operator: Role = {
+        id: 'operator',
+        name: 'Operator',
+        description: 'Everything on alpha except delete',
+        allowedServers: ['alpha'],
+        systemInstruction: '',
+        toolPermissions: { deny: ['alpha__delete'] }
+      };
+      lenient.registerRole(operator);
+      const visibility = new ToolVisibilityManager(testLogger, lenient);
+      visibility.registerToolsFromList([createTool('alpha__read'), createTool('alpha__delete')]);
+      visibility.setCurrentRole(operator);
+
+      for (const tool of ['alpha__read', 'alpha__delete']) {
+        expect(visibility.isVisible(tool)).toBe(lenient.isToolAllowedForRole('operator', tool, 'alpha'));
+      }
+      expect(visibility.isVisible('alpha__read')).toBe(true);
+    });
+  });
+});
@@ -26,6 +26,71 @@ export interface ToolPermissions {
   denyPatterns?: string[];
 }
 
+/**
+ * Append entries to one list of a ToolPermissions, skipping duplicates
+ * Returns a new object; the input is not modified
+ */
+function addToolPermissionEntries(
+  permissions: ToolPermissions,
+  key: keyof ToolPermissions,
+  entries: string[]
+): ToolPermissions {
+  const existing = permissions[key] ?? [];
+  return { ...permissions, [key]: [...new Set([...existing, ...entries])] };
+}
+
+/**
+ * Allow a tool by exact name
+ */
+export function allowTool(permissions: ToolPermissions, toolName: string): ToolPermissions {
+  return addToolPermissionEntries(permissions, 'allow', [toolName]);
+}
+
+/**
+ * Deny a tool by exact name
+ */
+export function denyTool(permissions: ToolPermissions, toolName: string): ToolPermissions {
+  return addToolPermissionEntries(permissions, 'deny', [toolName]);
+}
+
+/**
+ * Allow tools matching a glob-style pattern
+ */
+export function allowPattern(permissions: ToolPermissions, pattern: string): ToolPermissions {
+  return addToolPermissionEntries(permissions, 'allowPatterns', [pattern]);
+}
+
+/**
+ * Deny tools matching a glob-style pattern
+ */
+export function denyPattern(permissions: ToolPermissions, pattern: string): ToolPermissions {
+  return addToolPermissionEntries(permissions, 'denyPatterns', [pattern]);
+}
+
+/**
+ * Combine two permission sets, keeping first-seen order and dropping duplicates
+ * Lists that are absent from both inputs stay absent in the result
+ */
+export function unionToolPermissions(a: ToolPermissions, b: ToolPermissions): ToolPermissions {
+  let result: ToolPermissions = { ...a };
+  for (const key of ['allow', 'deny', 'allowPatterns', 'denyPatterns'] as const) {
+    if (a[key] || b[key]) {
+      result = addToolPermissionEntries(result, key, [...(a[key] ?? []), ...(b[key] ?? [])]);
+    }
+  }
+  return result;
+}
+
+/**
+ * Whether a permission set has no allow or deny entries at all
+ */
+export function isEmptyToolPermissions(permissions: ToolPermissions): boolean {
+  return !permissions.allow?.length &&
+    !permissions.deny?.length &&
+    !permissions.allowPatterns?.length &&
+    !permissions.denyPatterns?.length;
+}
+
 /**
  * Role metadata for management and auditing
  */
@@ -53,6 +118,12 @@ export interface RoleMetadata {
 
   /** Skills assigned to this role */
   skills?: string[];
+
+  /** Whether this is a built-in system role */
+  system?: boolean;
+
+  /** Daily window in which the role can be activated ("HH:MM-HH:MM", local time) */
+  allowedTime?: string;
 }
 
 /**
@@ -87,6 +158,45 @@ export interface Role {
   metadata?: RoleMetadata;
 }
 
+/**
+ * Read a role's priority from its metadata
+ * Accepts integers and integer strings (config files are not always typed); anything else is undefined
+ */
+export function getRolePriority(role: Pick<Role, 'metadata'>): number | undefined {
+  const value: unknown = role.metadata?.priority;
+  if (typeof value === 'number') {
+    return Number.isInteger(value) ? value : undefined;
+  }
+  if (typeof value === 'string' && /^-?\d+$/.test(value.trim())) {
+    return parseInt(value, 10);
+  }
+  return undefined;
+}
+
+/**
+ * Check whether a role is a system role (metadata.system === true or a 'system' tag)
+ */
+export function isSystemRole(role: Pick<Role, 'metadata'>): boolean {
+  const metadata = role.metadata;
+  if (!metadata) return false;
+  return (metadata.system as unknown) === true ||
+    (Array.isArray(metadata.tags) && metadata.tags.includes('system'));
+}
+
+/**
+ * Order roles by priority (highest first), then by ID; roles without a priority come last
+ */
+export function compareRolesByPriority(a: Pick<Role, 'id' | 'metadata'>, b: Pick<Role, 'id' | 'metadata'>): number {
+  const pa = getRolePriority(a);
+  const pb = getRolePriority(b);
+  if (pa !== pb) {
+    if (pa === undefined) return 1;
+    if (pb === undefined) return -1;
+    return pb - pa;
+  }
+  return a.id.localeCompare(b.id);
+}
+
 /**
  * Configuration for fetching system instruction from a remote MCP server
  */
@@ -120,6 +230,15 @@ export interface RemoteInstruction {
  */
 export type MemoryPolicy = 'none' | 'isolated' | 'team' | 'all';
 
+const MEMORY_POLICY_ORDER: MemoryPolicy[] = ['none', 'isolated', 'team', 'all'];
+
+/**
+ * Rank a memory policy by privilege (0 = 'none', 3 = 'all')
+ */
+export function memoryPolicyRank(policy: MemoryPolicy): number {
+  return MEMORY_POLICY_ORDER.indexOf(policy);
+}
+
 /**
  * Capability grants from skills
  */
@@ -187,6 +306,103 @@ export interface SkillCommand {
 // Tool Types
 // ============================================================================
 
+/**
+ * Why a tool is visible or hidden
+ * - 'discovered': Registered but not yet filtered
+ * - 'no_role': No role is active (all tools shown)
+ * - 'server_not_allowed': Role has no access to the tool's server
+ * - 'explicitly_denied': Tool is in the role's deny list
+ * - 'denied_by_pattern': Tool matches one of the role's deny patterns
+ * - 'not_in_allow_list': Role has permissions but none allow the tool
+ * - 'allowed_by_permission': Tool matches the role's allow list or patterns
+ * - 'server_access_granted': Role has no tool permissions, server access implies the tool
+ * - 'skill_restricted': Role allows the tool but no active skill does
+ * - 'backend_unavailable': Tool's server failed to start (not an RBAC decision)
+ * - 'memory_granted': Memory tool granted by a skill's memory policy
+ */
+export type VisibilityReason =
+  | 'discovered'
+  | 'no_role'
+  | 'server_not_allowed'
+  | 'explicitly_denied'
+  | 'denied_by_pattern'
+  | 'not_in_allow_list'
+  | 'allowed_by_permission'
+  | 'server_access_granted'
+  | 'skill_restricted'
+  | 'backend_unavailable'
+  | 'memory_granted'
+  | 'system_tool';
+
+const VISIBILITY_REASON_MESSAGES: Record<VisibilityReason, string> = {
+  discovered: 'Tool discovered, not yet filtered',
+  no_role: 'No role is active',
+  server_not_allowed: 'Server is not allowed for this role',
+  explicitly_denied: 'Tool is explicitly denied',
+  denied_by_pattern: 'Tool is denied by pattern',
+  not_in_allow_list: 'Tool is not in the allow list',
+  allowed_by_permission: 'Tool is allowed by role permissions',
+  server_access_granted: 'Server access grants all of its tools',
+  skill_restricted: 'Tool is not allowed by active skills',
+  backend_unavailable: 'Backend server is unavailable',
+  memory_granted: 'Memory access granted by skill',
+  system_tool: 'System tool, always visible'
+};
+
+/**
+ * Get a human-readable message for a visibility reason
+ */
+export function describeVisibilityReason(reason: VisibilityReason): string {
+  return VISIBILITY_REASON_MESSAGES[reason];
+}
+
+/**
+ * Router management tools that are visible regardless of role
+ */
+export const DEFAULT_SYSTEM_TOOLS: readonly string[] = [
+  'mycelium-router__get_context',
+  'mycelium-router__list_roles',
+  'mycelium-router__set_active_skills',
+  'mycelium-router__get_active_skills',
+  'mycelium-router__list_skills',
+  'mycelium-router__suggest_skills',
+  'mycelium-router__set_role'
+];
+
+/**
+ * Registry of always-visible system tools
+ * Starts with DEFAULT_SYSTEM_TOOLS; integrators can register their own
+ */
+export class SystemTools {
+  private names: Set<string>;
+
+  constructor(names: Iterable<string> = DEFAULT_SYSTEM_TOOLS) {
+    this.names = new Set(names);
+  }
+
+  /**
+   * Make a tool always visible
+   */
+  register(name: string): void {
+    this.names.add(name);
+  }
+
+  /**
+   * Check whether a tool is a system tool
+   * Exact names only: a backend tool such as 'evil__set_role' must not inherit system visibility
+   */
+  isSystemTool(name: string): boolean {
+    return this.names.has(name);
+  }
+
+  /**
+   * List registered system tool names
+   */
+  list(): string[] {
+    return Array.from(this.names);
+  }
+}
+
 /**
  * Extended tool information with source tracking
  */
@@ -204,7 +420,10 @@ export interface ToolInfo {
   visible: boolean;
 
   /** Why this tool is visible/hidden */
-  visibilityReason?: string;
+  visibilityReason?: VisibilityReason;
+
+  /** When the tool was last registered from its server's tool list */
+  registeredAt?: Date;
 }
 
 // ============================================================================
@@ -248,13 +467,214 @@ export class ToolNotAccessibleError extends Error {
   constructor(
     public readonly toolName: string,
     public readonly currentRole: string,
-    public readonly reason: string
+    public readonly reason: string,
+    public readonly visibilityReason?: VisibilityReason
   ) {
     super(`Tool '${toolName}' is not accessible for role '${currentRole}': ${reason}`);
     this.name = 'ToolNotAccessibleError';
   }
 }
 
+/**
+ * Error thrown when a role's memory policy does not cover another role's memory
+ */
+export class MemoryAccessDeniedError extends Error {
+  constructor(
+    public readonly accessorRole: string,
+    public readonly targetRole: string,
+    public readonly action: 'read' | 'write'
+  ) {
+    super(
+      `Role '${accessorRole}' cannot ${action === 'read' ? 'access' : 'write to'} ` +
+      `memory of role '${targetRole}'`
+    );
+    this.name = 'MemoryAccessDeniedError';
+  }
+}
+
+/**
+ * Error thrown when a role switch would raise the memory policy mid-session
+ */
+export class MemoryEscalationError extends Error {
+  constructor(
+    public readonly fromRole: string,
+    public readonly toRole: string,
+    public readonly fromPolicy: MemoryPolicy,
+    public readonly toPolicy: MemoryPolicy
+  ) {
+    super(
+      `Switching from role '${fromRole}' to '${toRole}' would raise memory access ` +
+      `from '${fromPolicy}' to '${toPolicy}'`
+    );
+    this.name = 'MemoryEscalationError';
+  }
+}
+
+/**
+ * Error thrown when a role switch is attempted before the cooldown has elapsed
+ */
+export class RoleSwitchCooldownError extends Error {
+  constructor(
+    public readonly roleId: string,
+    public readonly retryAfterMs: number
+  ) {
+    super(`Role switch to '${roleId}' rejected: retry in ${Math.ceil(retryAfterMs / 1000)}s`);
+    this.name = 'RoleSwitchCooldownError';
+  }
+}
+
+/**
+ * Error thrown when a role is activated outside its allowed time window
+ */
+export class RoleOutsideTimeWindowError extends Error {
+  constructor(
+    public readonly roleId: string,
+    public readonly window: string
+  ) {
+    super(`Role '${roleId}' can only be activated during ${window}`);
+    this.name = 'RoleOutsideTimeWindowError';
+  }
+}
+
+/**
+ * Error thrown when merging skill manifests that define the same skill IDs
+ */
+export class SkillManifestConflictError extends Error {
+  constructor(public readonly skillIds: string[]) {
+    super(`Skill manifests both define: ${skillIds.join(', ')}`);
+    this.name = 'SkillManifestConflictError';
+  }
+}
+
+/**
+ * Error thrown when a tool pattern cannot be used for matching
+ */
+export class InvalidPatternError extends Error {
+  constructor(
+    public readonly pattern: string,
+    reason: string
+  ) {
+    super(`Invalid tool pattern ${JSON.stringify(pattern)}: ${reason}`);
+    this.name = 'InvalidPatternError';
+  }
+}
+
+/**
+ * Reason a role definition is malformed
+ */
+export type RoleValidationIssue = 'empty_id' | 'control_characters' | 'self_inheritance';
+
+/**
+ * Error thrown when a role definition is malformed
+ */
+export class RoleValidationError extends Error {
+  constructor(
+    public readonly roleId: string,
+    public readonly issue: RoleValidationIssue
+  ) {
+    const messages: Record<RoleValidationIssue, string> = {
+      empty_id: 'role ID must not be empty',
+      control_characters: 'role ID must not contain control characters',
+      self_inheritance: 'role must not inherit from itself'
+    };
+    super(`Invalid role ${JSON.stringify(roleId)}: ${messages[issue]}`);
+    this.name = 'RoleValidationError';
+  }
+}
+
+/**
+ * Error thrown when a role is granted two mutually exclusive skills
+ */
+export class SkillConflictError extends Error {
+  constructor(
+    public readonly roleId: string,
+    public readonly skillId: string,
+    public readonly conflictingSkillId: string
+  ) {
+    super(`Role '${roleId}' cannot have both skills '${skillId}' and '${conflictingSkillId}'`);
+    this.name = 'SkillConflictError';
+  }
+}
+
+/**
+ * Error thrown when a request targets an upstream server that is not running
+ */
+export class ServerNotStartedError extends Error {
+  constructor(public readonly serverName: string) {
+    super(`Server '${serverName}' is not started`);
+    this.name = 'ServerNotStartedError';
+  }
+}
+
+/**
+ * Error thrown when an upstream server answers with something that is not a valid MCP response
+ */
+export class MalformedResponseError extends Error {
+  constructor(
+    public readonly serverName: string,
+    public readonly detail: string
+  ) {
+    super(`Malformed response from server '${serverName}': ${detail}`);
+    this.name = 'MalformedResponseError';
+  }
+}
+
+/**
+ * Error thrown when a tool call fails: a JSON-RPC error, a timeout, or a transport error
+ * The underlying failure is kept as `cause`
+ */
+export class ToolCallFailedError extends Error {
+  constructor(
+    public readonly serverName: string,
+    public readonly toolName: string,
+    public readonly code: number | undefined,
+    detail: string,
+    cause?: unknown
+  ) {
+    super(`Tool '${toolName}' on server '${serverName}' failed: ${detail}`, { cause });
+    this.name = 'ToolCallFailedError';
+  }
+}
+
+/**
+ * Error thrown when a ${VAR} placeholder references an undefined variable
+ */
+export class UndefinedEnvVariableError extends Error {
+  constructor(
+    public readonly variable: string,
+    public readonly envKey: string
+  ) {
+    super(`Environment variable '${variable}' referenced by '${envKey}' is not defined`);
+    this.name = 'UndefinedEnvVariableError';
+  }
+}
+
+/**
+ * Error thrown when a configuration fails validation
+ */
+export class ConfigValidationError extends Error {
+  constructor(public readonly issues: ConfigIssue[]) {
+    super(
+      `Invalid configuration:\n` +
+      issues.map(issue => `  - ${issue.server}: ${issue.message}`).join('\n')
+    );
+    this.name = 'ConfigValidationError';
+  }
+}
+
+/**
+ * Error thrown when a time window string cannot be parsed
+ */
+export class InvalidTimeWindowError extends Error {
+  constructor(
+    public readonly spec: string,
+    reason: string
+  ) {
+    super(`Invalid time window '${spec}': ${reason}`);
+    this.name = 'InvalidTimeWindowError';
+  }
+}
+
 // ============================================================================
 // Logger Interface
 // ============================================================================
@@ -334,6 +754,9 @@ export interface BaseSkillDefinition {
   /** Capability grants (memory, etc.) */
   grants?: SkillGrants;
 
+  /** Skill IDs that must never be granted to the same role as this skill */
+  conflicts?: string[];
+
   /** Custom slash commands provided by this skill */
   commands?: SkillCommand[];
 
@@ -341,6 +764,77 @@ export interface BaseSkillDefinition {
   metadata?: SkillMetadata;
 }
 
+/** Compiled glob patterns, keyed by pattern text */
+const globCache = new Map<string, RegExp>();
+
+function globToRegExp(pattern: string): RegExp {
+  let regex = globCache.get(pattern);
+  if (!regex) {
+    const source = pattern
+      .split('*')
+      .map(part => part.replace(/[.+?^${}()|[\]\\]/g, '\\$&'))
+      .join('.*');
+    regex = new RegExp(`^${source}$`);
+    globCache.set(pattern, regex);
+  }
+  return regex;
+}
+
+/**
+ * Match a tool name against a glob pattern
+ * - '*' matches any run of characters (including none)
+ * - Every other character matches itself literally, including regex metacharacters
+ * - Patterns without '*' are compared with plain string equality
+ */
+export function globMatch(pattern: string, name: string): boolean {
+  if (!pattern.includes('*')) {
+    return pattern === name;
+  }
+  return globToRegExp(pattern).test(name);
+}
+
+/**
+ * Validate a glob pattern and compile it to the RegExp globMatch uses
+ * globMatch treats '?', '[...]' and '{...}' literally, so a pattern written for another
+ * glob dialect would silently match nothing; those are rejected here instead.
+ * @throws InvalidPatternError if the pattern is empty, has whitespace, or uses unsupported syntax
+ */
+export function compileGlobPattern(pattern: string): RegExp {
+  if (pattern.trim().length === 0) {
+    throw new InvalidPatternError(pattern, 'pattern is empty');
+  }
+  if (/\s/.test(pattern)) {
+    throw new InvalidPatternError(pattern, 'pattern contains whitespace');
+  }
+  const unsupported = /[?[\]{}]/.exec(pattern);
+  if (unsupported) {
+    throw new InvalidPatternError(pattern, `'${unsupported[0]}' is not supported; only '*' is a wildcard`);
+  }
+  return globToRegExp(pattern);
+}
+
+/**
+ * Expand a skill's allowedTools against a catalog of known tool names
+ * Exact entries are kept even if missing from the catalog; wildcards only match catalog entries
+ */
+export function getEffectiveTools(skill: Pick<BaseSkillDefinition, 'allowedTools'>, catalog: string[]): string[] {
+  const tools = new Set<string>();
+
+  for (const entry of skill.allowedTools) {
+    if (!entry.includes('*')) {
+      tools.add(entry);
+      continue;
+    }
+    for (const name of catalog) {
+      if (globMatch(entry, name)) {
+        tools.add(name);
+      }
+    }
+  }
+
+  return Array.from(tools);
+}
+
 /**
  * Result of list_skills from Skill MCP Server
  */
@@ -353,6 +847,90 @@ export interface SkillManifest<TSkill = BaseSkillDefinition> {
 
   /** When the manifest was generated */
   generatedAt: Date;
+
+  /** Manifest schema version (defaults to SKILL_MANIFEST_SCHEMA_VERSION) */
+  schemaVersion?: number;
+}
+
+/** Current skill manifest schema version */
+export const SKILL_MANIFEST_SCHEMA_VERSION = 1;
+
+/**
+ * Upgrade a raw skill manifest (parsed JSON/YAML) to the current schema
+ * - Schema 0: memory policy lived on the skill itself (memory, memoryTeamRoles)
+ *   instead of under grants; skills without it get { memory: 'none' }
+ * - Schema 1: current shape; a missing schemaVersion is treated as 1
+ * A bare list of skills is accepted as a manifest
+ */
+export function migrateSkillManifest(raw: unknown): SkillManifest {
+  const data = (Array.isArray(raw) ? { skills: raw } : raw) as Record<string, unknown> | null;
+  if (!data || typeof data !== 'object' || !Array.isArray(data.skills)) {
+    throw new Error("Invalid skill manifest: missing 'skills' list");
+  }
+
+  const schemaVersion = typeof data.schemaVersion === 'number'
+    ? data.schemaVersion
+    : SKILL_MANIFEST_SCHEMA_VERSION;
+  if (schemaVersion > SKILL_MANIFEST_SCHEMA_VERSION) {
+    throw new Error(
+      `Unsupported skill manifest schema version ${schemaVersion} (latest: ${SKILL_MANIFEST_SCHEMA_VERSION})`
+    );
+  }
+
+  const skills = (data.skills as Array<Record<string, any>>).map(entry => {
+    const { memory, memoryTeamRoles, ...skill } = entry;
+    if (schemaVersion < 1 && !skill.grants) {
+      skill.grants = memory ? { memory, memoryTeamRoles } : { memory: 'none' };
+    }
+    return {
+      ...(schemaVersion < 1 ? skill : entry),
+      allowedRoles: entry.allowedRoles ?? [],
+      allowedTools: entry.allowedTools ?? []
+    } as BaseSkillDefinition;
+  });
+
+  return {
+    skills,
+    version: typeof data.version === 'string' ? data.version : '1.0.0',
+    generatedAt: data.generatedAt ? new Date(data.generatedAt as string | Date) : new Date(),
+    schemaVersion: SKILL_MANIFEST_SCHEMA_VERSION
+  };
+}
+
+/**
+ * How mergeSkillManifests handles a skill ID defined in both manifests
+ * - 'override': The overlay's definition replaces the base one
+ * - 'reject': Throw SkillManifestConflictError
+ */
+export type SkillManifestConflictPolicy = 'override' | 'reject';
+
+/**
+ * Merge an overlay manifest (e.g. a team's skills) into a base manifest by skill ID
+ * Base order is kept, overridden skills are replaced in place, and new skills are appended.
+ * The merged version is "<base>+<overlay>" so either input changing changes it.
+ * @throws SkillManifestConflictError on shared IDs under the 'reject' policy
+ */
+export function mergeSkillManifests(
+  base: SkillManifest,
+  overlay: SkillManifest,
+  onConflict: SkillManifestConflictPolicy
+): SkillManifest {
+  const overlayById = new Map(overlay.skills.map(skill => [skill.id, skill]));
+  const conflicts = base.skills.filter(skill => overlayById.has(skill.id)).map(skill => skill.id);
+  if (conflicts.length > 0 && onConflict === 'reject') {
+    throw new SkillManifestConflictError(conflicts);
+  }
+
+  const baseIds = new Set(base.skills.map(skill => skill.id));
+  return {
+    skills: [
+      ...base.skills.map(skill => overlayById.get(skill.id) ?? skill),
+      ...overlay.skills.filter(skill => !baseIds.has(skill.id))
+    ],
+    version: `${base.version}+${overlay.version}`,
+    generatedAt: new Date(),
+    schemaVersion: SKILL_MANIFEST_SCHEMA_VERSION
+  };
 }
 
 /**
@@ -468,6 +1046,168 @@ export interface DesktopConfig {
   mcpServers: Record<string, MCPServerConfig>;
 }
 
+/**
+ * Normalize a parsed desktop config
+ * Accepts either the camelCase `mcpServers` key or snake_case `mcp_servers`
+ * (camelCase wins if both are present); a config with neither has no servers
+ */
+export function parseDesktopConfig(raw: unknown): DesktopConfig {
+  if (!raw || typeof raw !== 'object' || Array.isArray(raw)) {
+    throw new Error('Invalid desktop config: expected a JSON object');
+  }
+  const data = raw as { mcpServers?: unknown; mcp_servers?: unknown };
+  const servers = data.mcpServers ?? data.mcp_servers ?? {};
+  if (typeof servers !== 'object' || servers === null || Array.isArray(servers)) {
+    throw new Error('Invalid desktop config: mcpServers must be an object');
+  }
+  return { mcpServers: servers as Record<string, MCPServerConfig> };
+}
+
+/**
+ * Separator between server and tool names in prefixed tool names (server__tool)
+ */
+export const TOOL_NAME_SEPARATOR = '__';
+
+/**
+ * A problem found while validating a desktop config
+ */
+export interface ConfigIssue {
+  /** Server the issue applies to */
+  server: string;
+
+  /** Human-readable description */
+  message: string;
+}
+
+/**
+ * Validate a desktop config
+ * Flags empty commands, server names that contain the tool name separator,
+ * and server names that differ only by case
+ */
+export function validateDesktopConfig(config: DesktopConfig): ConfigIssue[] {
+  const issues: ConfigIssue[] = [];
+  const seen = new Map<string, string>();
+
+  for (const [name, serverConfig] of Object.entries(config.mcpServers)) {
+    if (!name.trim()) {
+      issues.push({ server: name, message: 'Server name must not be empty' });
+    }
+    if (name.includes(TOOL_NAME_SEPARATOR)) {
+      issues.push({
+        server: name,
+        message: `Server name must not contain '${TOOL_NAME_SEPARATOR}' (used to prefix tool names)`
+      });
+    }
+
+    const key = name.toLowerCase();
+    const existing = seen.get(key);
+    if (existing !== undefined) {
+      issues.push({ server: name, message: `Duplicate server name (conflicts with '${existing}')` });
+    } else {
+      seen.set(key, name);
+    }
+
+    if (typeof serverConfig?.command !== 'string' || !serverConfig.command.trim()) {
+      issues.push({ server: name, message: 'Command must not be empty' });
+    }
+  }
+
+  return issues;
+}
+
+/**
+ * Lookup for environment variable values (e.g. name => process.env[name])
+ */
+export type EnvSource = (name: string) => string | undefined;
+
+/**
+ * Expand ${VAR} placeholders in a server's env values
+ * Text outside placeholders is left untouched
+ * @throws UndefinedEnvVariableError if a referenced variable is not defined
+ */
+export function resolveServerEnv(config: MCPServerConfig, source: EnvSource): MCPServerConfig {
+  if (!config.env) {
+    return { ...config };
+  }
+
+  const env: Record<string, string> = {};
+  for (const [key, value] of Object.entries(config.env)) {
+    env[key] = value.replace(/\$\{([A-Za-z_][A-Za-z0-9_]*)\}/g, (_match, name: string) => {
+      const resolved = source(name);
+      if (resolved === undefined) {
+        throw new UndefinedEnvVariableError(name, key);
+      }
+      return resolved;
+    });
+  }
+
+  return { ...config, env };
+}
+
+// ============================================================================
+// Time Windows
+// ============================================================================
+
+/**
+ * Daily time window in minutes since midnight
+ * When end < start the window wraps past midnight (e.g. 22:00-02:00); start and end never match
+ */
+export interface TimeWindow {
+  /** Start of the window, inclusive */
+  start: number;
+
+  /** End of the window, exclusive */
+  end: number;
+}
+
+const TIME_OF_DAY = /^([01]\d|2[0-3]):([0-5]\d)$/;
+
+function parseTimeOfDay(spec: string, value: string): number {
+  const match = TIME_OF_DAY.exec(value.trim());
+  if (!match) {
+    throw new InvalidTimeWindowError(spec, `'${value.trim()}' is not a HH:MM time`);
+  }
+  return Number(match[1]) * 60 + Number(match[2]);
+}
+
+/**
+ * Parse a "HH:MM-HH:MM" time window
+ * @throws InvalidTimeWindowError if the string is malformed or the window is empty
+ */
+export function parseTimeWindow(spec: string): TimeWindow {
+  const parts = spec.split('-');
+  if (parts.length !== 2) {
+    throw new InvalidTimeWindowError(spec, 'expected HH:MM-HH:MM');
+  }
+
+  const start = parseTimeOfDay(spec, parts[0]);
+  const end = parseTimeOfDay(spec, parts[1]);
+  if (start === end) {
+    throw new InvalidTimeWindowError(spec, 'start and end are the same');
+  }
+  return { start, end };
+}
+
+/**
+ * Whether a time (local time of day of the given Date) falls inside the window
+ */
+export function isWithinTimeWindow(window: TimeWindow, time: Date): boolean {
+  const minutes = time.getHours() * 60 + time.getMinutes();
+  if (window.start < window.end) {
+    return minutes >= window.start && minutes < window.end;
+  }
+  return minutes >= window.start || minutes < window.end;
+}
+
+/**
+ * Read a role's allowed time window from its metadata
+ * @throws InvalidTimeWindowError if metadata.allowedTime is malformed
+ */
+export function getRoleTimeWindow(role: Pick<Role, 'metadata'>): TimeWindow | undefined {
+  const spec = role.metadata?.allowedTime;
+  return spec === undefined ? undefined : parseTimeWindow(spec);
+}
+
 // ============================================================================
 // Session-based Skill Management Types
 // ============================================================================
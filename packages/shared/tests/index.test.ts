@@ -8,6 +8,34 @@ import {
   RoleNotFoundError,
   ServerNotAccessibleError,
   ToolNotAccessibleError,
+  describeVisibilityReason,
+  validateDesktopConfig,
+  resolveServerEnv,
+  globMatch,
+  parseDesktopConfig,
+  getRolePriority,
+  isSystemRole,
+  compareRolesByPriority,
+  migrateSkillManifest,
+  SKILL_MANIFEST_SCHEMA_VERSION,
+  getEffectiveTools,
+  allowTool,
+  denyTool,
+  allowPattern,
+  unionToolPermissions,
+  isEmptyToolPermissions,
+  parseTimeWindow,
+  isWithinTimeWindow,
+  getRoleTimeWindow,
+  InvalidTimeWindowError,
+  mergeSkillManifests,
+  SkillManifestConflictError,
+  compileGlobPattern,
+  InvalidPatternError,
+  SystemTools,
+  DEFAULT_SYSTEM_TOOLS,
+  UndefinedEnvVariableError,
+  ConfigValidationError,
   type Role,
   type ToolPermissions,
   type RoleMetadata,
@@ -123,6 +151,24 @@ describe('@mycelium/shared', () => {
       expect(error.toolName).toBe('filesystem__write_file');
       expect(error.message).toContain('filesystem__write_file');
     });
+
+    it('should carry a structured visibility reason', () => {
+      const error = new ToolNotAccessibleError(
+        'filesystem__delete_file',
+        'guest',
+        'denied',
+        'explicitly_denied'
+      );
+
+      expect(error.visibilityReason).toBe('explicitly_denied');
+    });
+  });
+
+  describe('describeVisibilityReason', () => {
+    it('should describe denial reasons', () => {
+      expect(describeVisibilityReason('server_not_allowed')).toBe('Server is not allowed for this role');
+      expect(describeVisibilityReason('denied_by_pattern')).toBe('Tool is denied by pattern');
+    });
   });
 
   describe('Type exports', () => {
@@ -239,7 +285,7 @@ describe('@mycelium/shared', () => {
         sourceServer: 'filesystem',
         prefixedName: 'filesystem__read_file',
         visible: true,
-        visibilityReason: 'allowed by role',
+        visibilityReason: 'allowed_by_permission',
       };
 
       expect(toolInfo.prefixedName).toBe('filesystem__read_file');
@@ -412,4 +458,343 @@ describe('@mycelium/shared', () => {
       expect(caught[0]).toBeInstanceOf(ToolNotAccessibleError);
     });
   });
+
+  describe('validateDesktopConfig', () => {
+    it('should accept a valid config', () => {
+      expect(validateDesktopConfig({
+        mcpServers: { filesystem: { command: 'npx', args: ['-y', 'fs'] } }
+      })).toEqual([]);
+    });
+
+    it('should flag an empty command', () => {
+      const issues = validateDesktopConfig({ mcpServers: { filesystem: { command: '  ' } } });
+      expect(issues).toEqual([{ server: 'filesystem', message: 'Command must not be empty' }]);
+    });
+
+    it('should flag a name containing the tool separator', () => {
+      const issues = validateDesktopConfig({ mcpServers: { 'file__system': { command: 'node' } } });
+      expect(issues).toHaveLength(1);
+      expect(issues[0].message).toContain("'__'");
+    });
+
+    it('should flag names that differ only by case', () => {
+      const issues = validateDesktopConfig({
+        mcpServers: {
+          filesystem: { command: 'node' },
+          FileSystem: { command: 'node' }
+        }
+      });
+      expect(issues).toEqual([
+        { server: 'FileSystem', message: "Duplicate server name (conflicts with 'filesystem')" }
+      ]);
+    });
+
+    it('should list every issue in ConfigValidationError', () => {
+      const error = new ConfigValidationError([
+        { server: 'a', message: 'Command must not be empty' },
+        { server: 'b__c', message: 'bad name' }
+      ]);
+      expect(error.name).toBe('ConfigValidationError');
+      expect(error.message).toContain('a: Command must not be empty');
+      expect(error.message).toContain('b__c: bad name');
+    });
+  });
+
+  describe('resolveServerEnv', () => {
+    const source = (name: string) => ({ API_KEY: 'secret-123', REGION: 'eu' } as Record<string, string>)[name];
+
+    it('should expand defined variables', () => {
+      const resolved = resolveServerEnv(
+        { command: 'node', env: { TOKEN: '${API_KEY}', URL: 'https://${REGION}.example.com' } },
+        source
+      );
+      expect(resolved.env).toEqual({ TOKEN: 'secret-123', URL: 'https://eu.example.com' });
+    });
+
+    it('should leave literal text intact', () => {
+      const config = { command: 'node', args: ['${API_KEY}'], env: { PRICE: '$5', RAW: 'plain' } };
+      const resolved = resolveServerEnv(config, source);
+
+      expect(resolved.env).toEqual({ PRICE: '$5', RAW: 'plain' });
+      expect(resolved.args).toEqual(['${API_KEY}']);
+      expect(config.env.PRICE).toBe('$5');
+    });
+
+    it('should fail on an undefined variable', () => {
+      expect(() => resolveServerEnv({ command: 'node', env: { TOKEN: '${MISSING}' } }, source))
+        .toThrow(UndefinedEnvVariableError);
+      expect(() => resolveServerEnv({ command: 'node', env: { TOKEN: '${MISSING}' } }, source))
+        .toThrow("Environment variable 'MISSING' referenced by 'TOKEN' is not defined");
+    });
+  });
+
+  describe('getEffectiveTools', () => {
+    const catalog = [
+      'filesystem__read_file',
+      'filesystem__write_file',
+      'filesystem__list_directory',
+      'filesystem__delete_file',
+      'filesystem__move_file',
+      'database__query',
+      'database__insert',
+      'database__drop_table'
+    ];
+
+    it('should expand a server wildcard against the catalog', () => {
+      const tools = getEffectiveTools({ allowedTools: ['filesystem__*'] }, catalog);
+      expect(tools).toEqual(catalog.slice(0, 5));
+    });
+
+    it('should keep exact entries and deduplicate', () => {
+      const tools = getEffectiveTools(
+        { allowedTools: ['database__query', 'database__*', 'git__commit'] },
+        catalog
+      );
+      expect(tools).toEqual(['database__query', 'database__insert', 'database__drop_table', 'git__commit']);
+    });
+
+    it('should treat regex characters literally', () => {
+      expect(globMatch('a.b__*', 'a.b__tool')).toBe(true);
+      expect(globMatch('a.b__*', 'axb__tool')).toBe(false);
+    });
+
+    it('should match + and ( literally', () => {
+      expect(globMatch('a+b', 'a+b')).toBe(true);
+      expect(globMatch('a+b', 'aaab')).toBe(false);
+      expect(globMatch('calc__a+b*', 'calc__a+b_sum')).toBe(true);
+      expect(globMatch('calc__a+b*', 'calc__aaab_sum')).toBe(false);
+      expect(globMatch('fn__(x)*', 'fn__(x)_call')).toBe(true);
+    });
+
+    it('should give the same answer for repeated checks of a cached pattern', () => {
+      for (let i = 0; i < 1000; i++) {
+        expect(globMatch('filesystem__*', `filesystem__tool_${i}`)).toBe(true);
+      }
+      expect(globMatch('filesystem__*', 'git__tool_0')).toBe(false);
+    });
+  });
+
+  describe('migrateSkillManifest', () => {
+    it('should load a schema 0 manifest without grants using default grants', () => {
+      const manifest = migrateSkillManifest({
+        schemaVersion: 0,
+        version: '0.9.0',
+        skills: [
+          { id: 'reader', displayName: 'Reader', description: 'Read files', allowedRoles: ['guest'], allowedTools: ['filesystem__read_file'] },
+          { id: 'lead', displayName: 'Lead', description: 'Team lead', allowedRoles: ['lead'], allowedTools: [], memory: 'team', memoryTeamRoles: ['guest'] }
+        ]
+      });
+
+      expect(manifest.schemaVersion).toBe(SKILL_MANIFEST_SCHEMA_VERSION);
+      expect(manifest.version).toBe('0.9.0');
+      expect(manifest.skills[0].grants).toEqual({ memory: 'none' });
+      expect(manifest.skills[1].grants).toEqual({ memory: 'team', memoryTeamRoles: ['guest'] });
+      expect(manifest.skills[1]).not.toHaveProperty('memory');
+    });
+
+    it('should treat a manifest without schemaVersion as current', () => {
+      const manifest = migrateSkillManifest([{ id: 'reader', allowedTools: ['filesystem__read_file'] }]);
+
+      expect(manifest.skills[0].grants).toBeUndefined();
+      expect(manifest.skills[0].allowedRoles).toEqual([]);
+    });
+
+    it('should reject newer schema versions', () => {
+      expect(() => migrateSkillManifest({ schemaVersion: 99, skills: [] }))
+        .toThrow('Unsupported skill manifest schema version 99');
+    });
+  });
+
+  describe('role metadata accessors', () => {
+    const role = (id: string, metadata?: Record<string, unknown>) =>
+      ({ id, metadata } as unknown as Role);
+
+    it('should read priority from numbers and integer strings', () => {
+      expect(getRolePriority(role('a', { priority: 10 }))).toBe(10);
+      expect(getRolePriority(role('a', { priority: ' -3 ' }))).toBe(-3);
+    });
+
+    it('should return undefined for missing or malformed priority', () => {
+      expect(getRolePriority(role('a'))).toBeUndefined();
+      expect(getRolePriority(role('a', { priority: 'high' }))).toBeUndefined();
+      expect(getRolePriority(role('a', { priority: 1.5 }))).toBeUndefined();
+    });
+
+    it('should detect system roles by flag or tag', () => {
+      expect(isSystemRole(role('a', { system: true }))).toBe(true);
+      expect(isSystemRole(role('a', { tags: ['system'] }))).toBe(true);
+      expect(isSystemRole(role('a', { system: 'yes' }))).toBe(false);
+      expect(isSystemRole(role('a'))).toBe(false);
+    });
+
+    it('should order roles by priority, then id', () => {
+      const roles = [role('c'), role('b', { priority: 1 }), role('a', { priority: 5 }), role('d', { priority: '1' })];
+
+      expect(roles.sort(compareRolesByPriority).map(r => r.id)).toEqual(['a', 'b', 'd', 'c']);
+    });
+  });
+
+  describe('parseDesktopConfig', () => {
+    it('should prefer mcpServers over mcp_servers', () => {
+      const config = parseDesktopConfig({
+        mcpServers: { a: { command: 'a' } },
+        mcp_servers: { b: { command: 'b' } }
+      });
+
+      expect(Object.keys(config.mcpServers)).toEqual(['a']);
+    });
+
+    it('should reject non-object configs', () => {
+      expect(() => parseDesktopConfig([])).toThrow('expected a JSON object');
+      expect(() => parseDesktopConfig({ mcpServers: [] })).toThrow('mcpServers must be an object');
+    });
+  });
+
+  describe('ToolPermissions helpers', () => {
+    it('should build permissions without mutating the input', () => {
+      const base: ToolPermissions = {};
+      const built = allowPattern(denyTool(allowTool(base, 'fs__read'), 'fs__delete'), 'git__*');
+
+      expect(built).toEqual({ allow: ['fs__read'], deny: ['fs__delete'], allowPatterns: ['git__*'] });
+      expect(base).toEqual({});
+      expect(allowTool(built, 'fs__read').allow).toEqual(['fs__read']);
+    });
+
+    it('should union two permission sets with deduplicated lists', () => {
+      const a: ToolPermissions = { allow: ['fs__read', 'fs__write'], deny: ['fs__delete'] };
+      const b: ToolPermissions = { allow: ['fs__write', 'git__log'], deny: ['fs__delete'], denyPatterns: ['shell__*'] };
+
+      expect(unionToolPermissions(a, b)).toEqual({
+        allow: ['fs__read', 'fs__write', 'git__log'],
+        deny: ['fs__delete'],
+        denyPatterns: ['shell__*']
+      });
+    });
+
+    it('should report empty permission sets', () => {
+      expect(isEmptyToolPermissions({})).toBe(true);
+      expect(isEmptyToolPermissions({ allow: [], denyPatterns: [] })).toBe(true);
+      expect(isEmptyToolPermissions(denyTool({}, 'fs__delete'))).toBe(false);
+    });
+  });
+
+  describe('time windows', () => {
+    const at = (hours: number, minutes: number) => new Date(2024, 0, 1, hours, minutes);
+
+    it('should contain times inside a same-day window', () => {
+      const window = parseTimeWindow('09:00-17:30');
+
+      expect(window).toEqual({ start: 540, end: 1050 });
+      expect(isWithinTimeWindow(window, at(9, 0))).toBe(true);
+      expect(isWithinTimeWindow(window, at(17, 29))).toBe(true);
+      expect(isWithinTimeWindow(window, at(17, 30))).toBe(false);
+      expect(isWithinTimeWindow(window, at(8, 59))).toBe(false);
+    });
+
+    it('should wrap windows past midnight', () => {
+      const window = parseTimeWindow('22:00-02:00');
+
+      expect(isWithinTimeWindow(window, at(23, 15))).toBe(true);
+      expect(isWithinTimeWindow(window, at(0, 0))).toBe(true);
+      expect(isWithinTimeWindow(window, at(1, 59))).toBe(true);
+      expect(isWithinTimeWindow(window, at(2, 0))).toBe(false);
+      expect(isWithinTimeWindow(window, at(12, 0))).toBe(false);
+    });
+
+    it('should reject invalid strings', () => {
+      for (const spec of ['', '09:00', '9:00-17:00', '24:00-01:00', '09:60-10:00', '09:00-10:00-11:00', '10:00-10:00']) {
+        expect(() => parseTimeWindow(spec)).toThrow(InvalidTimeWindowError);
+      }
+    });
+
+    it('should read a role window from metadata', () => {
+      expect(getRoleTimeWindow({ metadata: { allowedTime: '09:00-17:30' } })).toEqual({ start: 540, end: 1050 });
+      expect(getRoleTimeWindow({ metadata: {} })).toBeUndefined();
+      expect(getRoleTimeWindow({})).toBeUndefined();
+    });
+  });
+
+  describe('mergeSkillManifests', () => {
+    const skill = (id: string, tools: string[]): BaseSkillDefinition => ({
+      id,
+      displayName: id,
+      description: `${id} skill`,
+      allowedRoles: ['developer'],
+      allowedTools: tools
+    });
+
+    const base: SkillManifest = {
+      skills: [skill('reader', ['fs__read']), skill('git', ['git__log'])],
+      version: '1.2.0',
+      generatedAt: new Date()
+    };
+    const overlay: SkillManifest = {
+      skills: [skill('reader', ['fs__read', 'fs__list']), skill('deploy', ['k8s__apply'])],
+      version: 'team-3',
+      generatedAt: new Date()
+    };
+
+    it('should let the overlay override shared skill IDs', () => {
+      const merged = mergeSkillManifests(base, overlay, 'override');
+
+      expect(merged.skills.map(s => s.id)).toEqual(['reader', 'git', 'deploy']);
+      expect(merged.skills[0].allowedTools).toEqual(['fs__read', 'fs__list']);
+      expect(merged.version).toBe('1.2.0+team-3');
+    });
+
+    it('should reject shared skill IDs under the reject policy', () => {
+      const error = (() => {
+        try {
+          mergeSkillManifests(base, overlay, 'reject');
+        } catch (e) {
+          return e;
+        }
+      })();
+
+      expect(error).toBeInstanceOf(SkillManifestConflictError);
+      expect((error as SkillManifestConflictError).skillIds).toEqual(['reader']);
+    });
+
+    it('should merge disjoint manifests under either policy', () => {
+      const disjoint = { ...overlay, skills: [skill('deploy', ['k8s__apply'])] };
+
+      expect(mergeSkillManifests(base, disjoint, 'reject').skills).toHaveLength(3);
+    });
+  });
+
+  describe('compileGlobPattern', () => {
+    it('should compile a valid pattern to the matcher globMatch uses', () => {
+      const regex = compileGlobPattern('filesystem__read*');
+
+      expect(regex.test('filesystem__read_file')).toBe(true);
+      expect(regex.test('filesystem__write_file')).toBe(false);
+    });
+
+    it('should reject unsupported glob syntax and blank patterns', () => {
+      expect(() => compileGlobPattern('filesystem__[read')).toThrow(InvalidPatternError);
+      expect(() => compileGlobPattern('filesystem__rea?')).toThrow("'?' is not supported");
+      expect(() => compileGlobPattern('git__{log,diff}')).toThrow(InvalidPatternError);
+      expect(() => compileGlobPattern('  ')).toThrow('pattern is empty');
+      expect(() => compileGlobPattern('git__ log')).toThrow('whitespace');
+    });
+  });
+
+  describe('SystemTools', () => {
+    it('should start with the default router tools', () => {
+      const systemTools = new SystemTools();
+
+      expect(systemTools.list()).toEqual([...DEFAULT_SYSTEM_TOOLS]);
+      expect(systemTools.isSystemTool('mycelium-router__set_role')).toBe(true);
+      expect(systemTools.isSystemTool('evil__set_role')).toBe(false);
+      expect(systemTools.isSystemTool('filesystem__read_file')).toBe(false);
+    });
+
+    it('should recognize registered custom tools by exact name', () => {
+      const systemTools = new SystemTools();
+      systemTools.register('ops__health');
+
+      expect(systemTools.isSystemTool('ops__health')).toBe(true);
+      expect(systemTools.isSystemTool('other__health')).toBe(false);
+    });
+  });
 });